@@ -1,5 +1,7 @@
 extern crate proc_macro;
 
+mod to_variable;
+
 use quote::quote;
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
@@ -23,4 +25,15 @@ pub fn derive_has_base_node(input: TokenStream) -> TokenStream {
     };
 
     output.into()
-}
\ No newline at end of file
+}
+
+/// Implements `pete_core::common::variable::ToVariable` for structs and enums.
+/// Named fields become map entries, see `to_variable` module for the attributes
+#[proc_macro_derive(ToVariable, attributes(pete))]
+pub fn derive_to_variable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match to_variable::expand(input) {
+        Ok(output) => output.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,177 @@
+//! `#[derive(ToVariable)]`
+//!
+//! Field attributes:
+//! - `#[pete(rename = "name")]` — a key of the field in the map
+//! - `#[pete(skip)]` — the field is not converted
+//! - `#[pete(flatten)]` — entries of the field's map are added to the outer map, a value which is not a map adds nothing
+//!
+//! Variants of enums accept `#[pete(rename = "name")]`. A unit variant becomes a string with its name,
+//! other variants become a map with a single entry `{"Variant": value}`
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Attribute, Data, DeriveInput, Fields, GenericParam, Generics, Ident, Lit, Meta, NestedMeta};
+use syn::spanned::Spanned;
+
+#[derive(Default)]
+struct FieldOptions {
+    rename: Option<String>,
+    skip: bool,
+    flatten: bool,
+}
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let generics = add_trait_bounds(input.generics.clone());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let body = match &input.data {
+        Data::Struct(data) => expand_struct(&data.fields)?,
+        Data::Enum(data) => {
+            let mut arms = Vec::new();
+            for variant in &data.variants {
+                let options = parse_options(&variant.attrs)?;
+                if options.skip || options.flatten {
+                    return Err(syn::Error::new(variant.span(), "Only `rename` is supported for enum variants"));
+                }
+                let name = options.rename.unwrap_or_else(|| variant.ident.to_string());
+                arms.push(expand_variant(ident, &variant.ident, &name, &variant.fields)?);
+            }
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        },
+        Data::Union(_) => return Err(syn::Error::new(input.span(), "ToVariable cannot be derived for unions")),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::pete_core::common::variable::ToVariable for #ident #ty_generics #where_clause {
+            fn to_variable(&self) -> ::pete_core::common::variable::Variable {
+                #body
+            }
+        }
+    })
+}
+
+fn add_trait_bounds(mut generics: Generics) -> Generics {
+    for param in &mut generics.params {
+        if let GenericParam::Type(type_param) = param {
+            type_param.bounds.push(syn::parse_quote!(::pete_core::common::variable::ToVariable));
+        }
+    }
+    generics
+}
+
+fn expand_struct(fields: &Fields) -> syn::Result<TokenStream> {
+    match fields {
+        Fields::Named(_) => {
+            let accessors: Vec<TokenStream> = fields.iter()
+                .map(|f| { let name = f.ident.as_ref().unwrap(); quote!(self.#name) })
+                .collect();
+            expand_named_fields(fields, &accessors)
+        },
+        Fields::Unnamed(_) => {
+            let accessors: Vec<TokenStream> = (0..fields.len())
+                .map(|i| { let index = syn::Index::from(i); quote!(self.#index) })
+                .collect();
+            Ok(expand_unnamed_fields(&accessors))
+        },
+        Fields::Unit => Ok(quote!(::pete_core::common::variable::Variable::Null)),
+    }
+}
+
+fn expand_variant(enum_ident: &Ident, variant_ident: &Ident, name: &str, fields: &Fields) -> syn::Result<TokenStream> {
+    let (pattern, value) = match fields {
+        Fields::Named(_) => {
+            let names: Vec<&Ident> = fields.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+            let accessors: Vec<TokenStream> = names.iter().map(|name| quote!((*#name))).collect();
+            (quote!(#enum_ident::#variant_ident { #(#names),* }), expand_named_fields(fields, &accessors)?)
+        },
+        Fields::Unnamed(_) => {
+            let bindings: Vec<Ident> = (0..fields.len()).map(|i| format_ident!("field_{}", i)).collect();
+            let accessors: Vec<TokenStream> = bindings.iter().map(|b| quote!((*#b))).collect();
+            (quote!(#enum_ident::#variant_ident(#(#bindings),*)), expand_unnamed_fields(&accessors))
+        },
+        Fields::Unit => return Ok(quote! {
+            #enum_ident::#variant_ident => ::pete_core::common::variable::Variable::from(#name),
+        }),
+    };
+    Ok(quote! {
+        #pattern => {
+            let mut pete_variant = ::pete_core::common::variable::VariableStore::new();
+            pete_variant.insert(::std::string::String::from(#name), #value);
+            ::pete_core::common::variable::Variable::Map(pete_variant)
+        },
+    })
+}
+
+fn expand_named_fields(fields: &Fields, accessors: &[TokenStream]) -> syn::Result<TokenStream> {
+    let mut statements = Vec::new();
+    for (field, accessor) in fields.iter().zip(accessors) {
+        let options = parse_options(&field.attrs)?;
+        if options.skip {
+            continue;
+        }
+        if options.flatten {
+            if options.rename.is_some() {
+                return Err(syn::Error::new(field.span(), "`rename` and `flatten` cannot be combined"));
+            }
+            statements.push(quote! {
+                if let ::pete_core::common::variable::Variable::Map(pete_map) = ::pete_core::common::variable::ToVariable::to_variable(&#accessor) {
+                    pete_store.extend(pete_map);
+                }
+            });
+        } else {
+            let key = options.rename.unwrap_or_else(|| field.ident.as_ref().unwrap().to_string());
+            statements.push(quote! {
+                pete_store.insert(::std::string::String::from(#key), ::pete_core::common::variable::ToVariable::to_variable(&#accessor));
+            });
+        }
+    }
+    Ok(quote! {
+        {
+            let mut pete_store = ::pete_core::common::variable::VariableStore::new();
+            #(#statements)*
+            ::pete_core::common::variable::Variable::Map(pete_store)
+        }
+    })
+}
+
+// A single field is converted as is, several fields become a list
+fn expand_unnamed_fields(accessors: &[TokenStream]) -> TokenStream {
+    if accessors.len() == 1 {
+        let accessor = &accessors[0];
+        quote!(::pete_core::common::variable::ToVariable::to_variable(&#accessor))
+    } else {
+        quote! {
+            ::pete_core::common::variable::Variable::List(vec![
+                #(::pete_core::common::variable::ToVariable::to_variable(&#accessors)),*
+            ])
+        }
+    }
+}
+
+fn parse_options(attrs: &[Attribute]) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+    for attr in attrs.iter().filter(|a| a.path.is_ident("pete")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(syn::Error::new(meta.span(), "Expected `#[pete(...)]`")),
+        };
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => options.skip = true,
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("flatten") => options.flatten = true,
+                NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("rename") => {
+                    match name_value.lit {
+                        Lit::Str(s) => options.rename = Some(s.value()),
+                        lit => return Err(syn::Error::new(lit.span(), "Expected a string, e.g. `rename = \"name\"`")),
+                    }
+                },
+                other => return Err(syn::Error::new(other.span(), "Unknown attribute, expected `rename`, `skip` or `flatten`")),
+            }
+        }
+    }
+    Ok(options)
+}
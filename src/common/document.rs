@@ -0,0 +1,226 @@
+//! Loading variables from JSON, TOML or YAML documents.
+//! Each format is available with a cargo feature of the same name: `json`, `toml` and `yaml`
+
+use std::fmt;
+use std::error::Error;
+
+use crate::common::variable::{Variable, VariableStore};
+
+/// An error of parsing a document or converting its values to variables
+#[derive(Debug)]
+pub struct DocumentError {
+    pub message: String,
+}
+
+impl DocumentError {
+    pub fn new(message: String) -> DocumentError {
+        DocumentError {
+            message,
+        }
+    }
+
+    fn unsupported(path: &str, type_name: &str) -> DocumentError {
+        DocumentError::new(format!("Unsupported value of type \"{}\" at {}", type_name, path))
+    }
+}
+
+impl fmt::Display for DocumentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for DocumentError {}
+
+/// Parses a JSON object to variables, e.g. `{"user": {"name": "John"}}`
+#[cfg(feature = "json")]
+pub fn from_json(text: &str) -> Result<VariableStore, DocumentError> {
+    let value: serde_json::Value = serde_json::from_str(text)
+        .map_err(|e| DocumentError::new(format!("Failed to parse JSON: {}", e)))?;
+    into_store(convert_json(value, ROOT_PATH)?)
+}
+
+/// Parses a TOML document to variables. Dates and times are not supported
+#[cfg(feature = "toml")]
+pub fn from_toml(text: &str) -> Result<VariableStore, DocumentError> {
+    let table: toml::Table = text.parse()
+        .map_err(|e| DocumentError::new(format!("Failed to parse TOML: {}", e)))?;
+    into_store(convert_toml(toml::Value::Table(table), ROOT_PATH)?)
+}
+
+/// Parses a YAML mapping to variables. Keys must be strings, numbers or booleans, tagged values are not supported
+#[cfg(feature = "yaml")]
+pub fn from_yaml(text: &str) -> Result<VariableStore, DocumentError> {
+    let value: serde_yaml::Value = serde_yaml::from_str(text)
+        .map_err(|e| DocumentError::new(format!("Failed to parse YAML: {}", e)))?;
+    into_store(convert_yaml(value, ROOT_PATH)?)
+}
+
+const ROOT_PATH: &str = "(root)";
+
+fn into_store(variable: Variable) -> Result<VariableStore, DocumentError> {
+    match variable {
+        Variable::Map(store) => Ok(store),
+        other => Err(DocumentError::new(format!("Expected a map at the document root, got {}", other.get_type_name()))),
+    }
+}
+
+// Path of a map value, e.g. `users[0].name`
+fn get_key_path(path: &str, key: &str) -> String {
+    if path == ROOT_PATH {
+        String::from(key)
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+// Path of a list item, e.g. `users[0]`
+fn get_index_path(path: &str, index: usize) -> String {
+    if path == ROOT_PATH {
+        format!("[{}]", index)
+    } else {
+        format!("{}[{}]", path, index)
+    }
+}
+
+#[cfg(feature = "json")]
+fn convert_json(value: serde_json::Value, path: &str) -> Result<Variable, DocumentError> {
+    use serde_json::Value;
+
+    Ok(match value {
+        Value::Null => Variable::Null,
+        Value::Bool(b) => Variable::from(b),
+        Value::Number(n) => if let Some(i) = n.as_i64() {
+            Variable::from(i)
+        } else if let Some(u) = n.as_u64() {
+            Variable::from(u)
+        } else {
+            match n.as_f64() {
+                Some(f) => Variable::from(f),
+                None => return Err(DocumentError::unsupported(path, "number")),
+            }
+        },
+        Value::String(s) => Variable::from(s),
+        Value::Array(items) => Variable::List(items.into_iter().enumerate()
+            .map(|(i, item)| convert_json(item, &get_index_path(path, i)))
+            .collect::<Result<_, _>>()?),
+        Value::Object(map) => {
+            let mut store = VariableStore::new();
+            for (key, item) in map {
+                let item = convert_json(item, &get_key_path(path, &key))?;
+                store.insert(key, item);
+            }
+            Variable::Map(store)
+        },
+    })
+}
+
+#[cfg(feature = "toml")]
+fn convert_toml(value: toml::Value, path: &str) -> Result<Variable, DocumentError> {
+    use toml::Value;
+
+    Ok(match value {
+        Value::Boolean(b) => Variable::from(b),
+        Value::Integer(i) => Variable::from(i),
+        Value::Float(f) => Variable::from(f),
+        Value::String(s) => Variable::from(s),
+        Value::Datetime(_) => return Err(DocumentError::unsupported(path, "datetime")),
+        Value::Array(items) => Variable::List(items.into_iter().enumerate()
+            .map(|(i, item)| convert_toml(item, &get_index_path(path, i)))
+            .collect::<Result<_, _>>()?),
+        Value::Table(table) => {
+            let mut store = VariableStore::new();
+            for (key, item) in table {
+                let item = convert_toml(item, &get_key_path(path, &key))?;
+                store.insert(key, item);
+            }
+            Variable::Map(store)
+        },
+    })
+}
+
+#[cfg(feature = "yaml")]
+fn convert_yaml(value: serde_yaml::Value, path: &str) -> Result<Variable, DocumentError> {
+    use serde_yaml::Value;
+
+    Ok(match value {
+        Value::Null => Variable::Null,
+        Value::Bool(b) => Variable::from(b),
+        Value::Number(n) => if let Some(i) = n.as_i64() {
+            Variable::from(i)
+        } else if let Some(u) = n.as_u64() {
+            Variable::from(u)
+        } else {
+            match n.as_f64() {
+                Some(f) => Variable::from(f),
+                None => return Err(DocumentError::unsupported(path, "number")),
+            }
+        },
+        Value::String(s) => Variable::from(s),
+        Value::Sequence(items) => Variable::List(items.into_iter().enumerate()
+            .map(|(i, item)| convert_yaml(item, &get_index_path(path, i)))
+            .collect::<Result<_, _>>()?),
+        Value::Mapping(mapping) => {
+            let mut store = VariableStore::new();
+            for (key, item) in mapping {
+                let key = match key {
+                    Value::String(s) => s,
+                    Value::Bool(b) => b.to_string(),
+                    Value::Number(n) => n.to_string(),
+                    _ => return Err(DocumentError::new(format!("Unsupported key at {}, keys must be strings, numbers or booleans", path))),
+                };
+                let item = convert_yaml(item, &get_key_path(path, &key))?;
+                store.insert(key, item);
+            }
+            Variable::Map(store)
+        },
+        Value::Tagged(tagged) => return Err(DocumentError::unsupported(path, &format!("tagged {}", tagged.tag))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_common_document_json() {
+        let store = from_json(r#"{"zip": "75001", "count": 3, "big": 18446744073709551615, "ratio": 3.0, "tags": [null, true], "user": {"b": 1, "a": 2}}"#).unwrap();
+        assert_eq!(store.keys().collect::<Vec<_>>(), vec!["zip", "count", "big", "ratio", "tags", "user"]);
+        assert_eq!(store["count"], Variable::Int(3));
+        assert_eq!(store["big"], Variable::Int(u64::MAX as i128));
+        assert_eq!(store["ratio"], Variable::Float(3.0));
+        assert_eq!(store["tags"].get_string_value(), "[null, true]");
+        assert_eq!(store["user"].get_string_value(), "{\"b\": 1, \"a\": 2}");
+
+        assert_eq!(from_json("[1]").err().unwrap().message, "Expected a map at the document root, got list");
+        assert!(from_json("{").err().unwrap().message.starts_with("Failed to parse JSON: "));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_common_document_toml() {
+        let store = from_toml("title = \"Blog\"\nrate = 1.0\n\n[[posts]]\nid = 2\n\n[[posts]]\nid = 1\n").unwrap();
+        assert_eq!(store.keys().collect::<Vec<_>>(), vec!["title", "rate", "posts"]);
+        assert_eq!(store["rate"], Variable::Float(1.0));
+        assert_eq!(store["posts"].get_string_value(), "[{\"id\": 2}, {\"id\": 1}]");
+
+        let err = from_toml("[[posts]]\nid = 1\n\n[[posts]]\ncreated = 1979-05-27\n").err().unwrap();
+        assert_eq!(err.message, "Unsupported value of type \"datetime\" at posts[1].created");
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_common_document_yaml() {
+        let store = from_yaml("name: John\nage: 42\nscore: 4.5\nroles:\n  - admin\n  - ~\n404: Not found\n").unwrap();
+        assert_eq!(store.keys().collect::<Vec<_>>(), vec!["name", "age", "score", "roles", "404"]);
+        assert_eq!(store["age"], Variable::Int(42));
+        assert_eq!(store["score"], Variable::Float(4.5));
+        assert_eq!(store["roles"].get_string_value(), "[\"admin\", null]");
+
+        let err = from_yaml("user:\n  avatar: !binary aGVsbG8=\n").err().unwrap();
+        assert_eq!(err.message, "Unsupported value of type \"tagged !binary\" at user.avatar");
+        let err = from_yaml("user:\n  [1, 2]: pair\n").err().unwrap();
+        assert_eq!(err.message, "Unsupported key at user, keys must be strings, numbers or booleans");
+    }
+}
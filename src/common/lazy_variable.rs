@@ -0,0 +1,39 @@
+use std::fmt;
+
+use crate::common::variable::Variable;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+
+pub type LazyFunction = dyn Fn() -> Result<Variable, EvaluationError> + Send + Sync;
+
+/// A value computed by a closure when a template references it, e.g. a count which needs a database query.
+/// The value is computed at most once per render, see `RenderContext::resolve`
+pub struct LazyVariable {
+    function: Box<LazyFunction>,
+}
+
+impl LazyVariable {
+    pub fn new<F>(function: F) -> LazyVariable
+        where F: Fn() -> Result<Variable, EvaluationError> + Send + Sync + 'static {
+        LazyVariable {
+            function: Box::new(function),
+        }
+    }
+
+    /// Computes the value. The result is not memoised, so it is computed on each call
+    pub fn evaluate(&self) -> Result<Variable, EvaluationError> {
+        (self.function)()
+    }
+}
+
+impl fmt::Debug for LazyVariable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LazyVariable")
+    }
+}
+
+/// Lazy variables are equal only if they are the same instance
+impl PartialEq for LazyVariable {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
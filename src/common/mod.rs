@@ -1 +1,7 @@
-pub mod variable;
\ No newline at end of file
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+pub mod document;
+pub mod lazy_variable;
+#[cfg(feature = "serde")]
+pub mod serializer;
+pub mod template_object;
+pub mod variable;
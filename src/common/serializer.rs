@@ -0,0 +1,401 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::error::Error;
+
+use serde::ser::{self, Serialize};
+
+use crate::common::variable::{Variable, VariableStore};
+
+/// Converts any serializable value to a variable.
+/// Structs and maps become maps, sequences and tuples become lists, `None` and `()` become null
+pub fn to_variable<T: Serialize + ?Sized>(value: &T) -> Result<Variable, SerializeError> {
+    value.serialize(VariableSerializer)
+}
+
+/// Converts a serializable struct or map to variables available in a template
+pub fn to_variable_store<T: Serialize + ?Sized>(value: &T) -> Result<VariableStore, SerializeError> {
+    match to_variable(value)? {
+        Variable::Map(store) => Ok(store),
+        other => Err(SerializeError::new(format!("Expected a struct or a map, got {}", other.get_type_name()))),
+    }
+}
+
+#[derive(Debug)]
+pub struct SerializeError {
+    pub message: String,
+}
+
+impl SerializeError {
+    pub fn new(message: String) -> SerializeError {
+        SerializeError {
+            message,
+        }
+    }
+}
+
+impl fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for SerializeError {}
+
+impl ser::Error for SerializeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        SerializeError::new(msg.to_string())
+    }
+}
+
+/// A serde serializer producing a `Variable`
+pub struct VariableSerializer;
+
+impl ser::Serializer for VariableSerializer {
+    type Ok = Variable;
+    type Error = SerializeError;
+    type SerializeSeq = SerializeList;
+    type SerializeTuple = SerializeList;
+    type SerializeTupleStruct = SerializeList;
+    type SerializeTupleVariant = SerializeVariant<SerializeList>;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeVariant<SerializeMap>;
+
+    fn serialize_bool(self, v: bool) -> Result<Variable, SerializeError> {
+        Ok(Variable::from(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Variable, SerializeError> {
+        Ok(Variable::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Variable, SerializeError> {
+        Ok(Variable::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Variable, SerializeError> {
+        Ok(Variable::from(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Variable, SerializeError> {
+        Ok(Variable::from(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Variable, SerializeError> {
+        Ok(Variable::from(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Variable, SerializeError> {
+        Ok(Variable::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Variable, SerializeError> {
+        Ok(Variable::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Variable, SerializeError> {
+        Ok(Variable::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Variable, SerializeError> {
+        Ok(Variable::from(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Variable, SerializeError> {
+        match i128::try_from(v) {
+            Ok(v) => Ok(Variable::from(v)),
+            Err(_) => Err(SerializeError::new(format!("Integer {} is too large", v))),
+        }
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Variable, SerializeError> {
+        Ok(Variable::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Variable, SerializeError> {
+        Ok(Variable::from(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Variable, SerializeError> {
+        Ok(Variable::from(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Variable, SerializeError> {
+        Ok(Variable::from(v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Variable, SerializeError> {
+        Ok(Variable::from(v.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<Variable, SerializeError> {
+        Ok(Variable::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Variable, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Variable, SerializeError> {
+        Ok(Variable::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Variable, SerializeError> {
+        Ok(Variable::Null)
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str) -> Result<Variable, SerializeError> {
+        Ok(Variable::from(variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<Variable, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _variant_index: u32,
+                                                        variant: &'static str, value: &T) -> Result<Variable, SerializeError> {
+        let mut store = VariableStore::new();
+        store.insert(String::from(variant), to_variable(value)?);
+        Ok(Variable::Map(store))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeList, SerializeError> {
+        Ok(SerializeList { items: Vec::with_capacity(len.unwrap_or(0)) })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeList, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SerializeList, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _variant_index: u32,
+                               variant: &'static str, len: usize) -> Result<SerializeVariant<SerializeList>, SerializeError> {
+        Ok(SerializeVariant { variant, inner: self.serialize_seq(Some(len))? })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeMap, SerializeError> {
+        Ok(SerializeMap { store: VariableStore::new(), key: None })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeMap, SerializeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, _variant_index: u32,
+                                variant: &'static str, len: usize) -> Result<SerializeVariant<SerializeMap>, SerializeError> {
+        Ok(SerializeVariant { variant, inner: self.serialize_map(Some(len))? })
+    }
+}
+
+pub struct SerializeList {
+    items: Vec<Variable>,
+}
+
+impl ser::SerializeSeq for SerializeList {
+    type Ok = Variable;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.items.push(to_variable(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Variable, SerializeError> {
+        Ok(Variable::List(self.items))
+    }
+}
+
+impl ser::SerializeTuple for SerializeList {
+    type Ok = Variable;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Variable, SerializeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeList {
+    type Ok = Variable;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Variable, SerializeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+pub struct SerializeMap {
+    store: VariableStore,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Variable;
+    type Error = SerializeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerializeError> {
+        self.key = Some(to_key(to_variable(key)?)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        let key = match self.key.take() {
+            Some(key) => key,
+            None => return Err(SerializeError::new(String::from("A map value is serialized before its key"))),
+        };
+        self.store.insert(key, to_variable(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Variable, SerializeError> {
+        Ok(Variable::Map(self.store))
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = Variable;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerializeError> {
+        self.store.insert(String::from(key), to_variable(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Variable, SerializeError> {
+        Ok(Variable::Map(self.store))
+    }
+}
+
+/// An enum variant with fields, serialized as `{"Variant": fields}`
+pub struct SerializeVariant<T> {
+    variant: &'static str,
+    inner: T,
+}
+
+impl SerializeVariant<SerializeList> {
+    fn wrap(self) -> Variable {
+        let mut store = VariableStore::new();
+        store.insert(String::from(self.variant), Variable::List(self.inner.items));
+        Variable::Map(store)
+    }
+}
+
+impl SerializeVariant<SerializeMap> {
+    fn wrap(self) -> Variable {
+        let mut store = VariableStore::new();
+        store.insert(String::from(self.variant), Variable::Map(self.inner.store));
+        Variable::Map(store)
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeList> {
+    type Ok = Variable;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<Variable, SerializeError> {
+        Ok(self.wrap())
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeMap> {
+    type Ok = Variable;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerializeError> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<Variable, SerializeError> {
+        Ok(self.wrap())
+    }
+}
+
+// Map keys must be scalars, they are converted to strings: `{1: "a"}` becomes `{"1": "a"}`
+fn to_key(key: Variable) -> Result<String, SerializeError> {
+    match key {
+        Variable::String(s) => Ok(s),
+        Variable::Boolean(_) | Variable::Int(_) | Variable::Float(_) => Ok(key.get_string_value()),
+        other => Err(SerializeError::new(format!("A map key must be a string, a number or a boolean, got {}", other.get_type_name()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Address {
+        city: String,
+        zip: u32,
+    }
+
+    #[derive(Serialize)]
+    enum Role {
+        Admin,
+        Guest(u8),
+        Editor { sections: Vec<&'static str> },
+    }
+
+    #[derive(Serialize)]
+    struct User {
+        name: &'static str,
+        age: Option<u8>,
+        score: f64,
+        address: Address,
+        tags: (&'static str, bool),
+        roles: Vec<Role>,
+    }
+
+    #[test]
+    fn test_common_serializer_struct() {
+        let user = User {
+            name: "John",
+            age: None,
+            score: 4.5,
+            address: Address { city: String::from("Paris"), zip: 75001 },
+            tags: ("new", true),
+            roles: vec![Role::Admin, Role::Guest(2), Role::Editor { sections: vec!["news"] }],
+        };
+        let store = to_variable_store(&user).unwrap();
+        assert_eq!(store.keys().collect::<Vec<_>>(), vec!["name", "age", "score", "address", "tags", "roles"]);
+        assert_eq!(store["age"], Variable::Null);
+        assert_eq!(store["score"], Variable::Float(4.5));
+        assert_eq!(store["address"].get_string_value(), "{\"city\": \"Paris\", \"zip\": 75001}");
+        assert_eq!(store["tags"].get_string_value(), "[\"new\", true]");
+        assert_eq!(store["roles"].get_string_value(), "[\"Admin\", {\"Guest\": 2}, {\"Editor\": {\"sections\": [\"news\"]}}]");
+    }
+
+    #[test]
+    fn test_common_serializer_map_keys() {
+        let mut map = BTreeMap::new();
+        map.insert(2, "two");
+        map.insert(1, "one");
+        assert_eq!(to_variable(&map).unwrap().get_string_value(), "{\"1\": \"one\", \"2\": \"two\"}");
+
+        let mut map = BTreeMap::new();
+        map.insert(vec![1], "list");
+        assert_eq!(to_variable(&map).err().unwrap().message, "A map key must be a string, a number or a boolean, got list");
+    }
+
+    #[test]
+    fn test_common_serializer_not_a_map() {
+        assert_eq!(to_variable_store(&vec![1, 2]).err().unwrap().message, "Expected a struct or a map, got list");
+        assert!(to_variable(&u128::MAX).is_err());
+    }
+}
@@ -0,0 +1,44 @@
+use std::fmt;
+
+use crate::common::variable::Variable;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::functions::NamedArgs;
+
+/// A Rust value which resolves its attributes and methods on demand while a template is rendered,
+/// so an object graph does not have to be copied to variables beforehand.
+/// Stored in a variable with `Variable::new_from_object`
+///
+/// `{{ user.profile }}` calls `get_attribute("profile")`, and if there is no such attribute,
+/// a method `profile` without arguments. `{{ order.total(currency="EUR") }}` calls `call_method`
+pub trait TemplateObject: Send + Sync {
+    /// Returns an attribute or `None` if the object has no attribute with this name
+    fn get_attribute(&self, name: &str) -> Option<Variable>;
+
+    /// Calls a method. Returns `None` if the object has no method with this name
+    fn call_method(&self, _name: &str, _args: &[Variable], _named_args: &NamedArgs) -> Option<Result<Variable, EvaluationError>> {
+        None
+    }
+
+    /// The object as printed in a template
+    fn get_string_value(&self) -> String {
+        String::from("[object]")
+    }
+
+    /// Whether the object is true in conditions
+    fn get_boolean_value(&self) -> bool {
+        true
+    }
+}
+
+impl fmt::Debug for dyn TemplateObject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TemplateObject({})", self.get_string_value())
+    }
+}
+
+/// Objects are equal only if they are the same instance
+impl PartialEq for dyn TemplateObject {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self as *const Self as *const u8, other as *const Self as *const u8)
+    }
+}
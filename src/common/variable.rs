@@ -1,149 +1,202 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
-pub type VariableStore = HashMap<String, Variable>;
+use indexmap::IndexMap;
 
-enum VariableType {
-    Boolean,
-    Float,
-    Int,
-    StringType,
-    Struct
-}
+pub use derive_macro::ToVariable;
 
-union Value {
-    boolean_value: bool,
-    float_value: f64,
-    int_value: i128,
-}
+use crate::common::lazy_variable::LazyVariable;
+use crate::common::template_object::TemplateObject;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+
+/// Named variables. Keys keep their insertion order, so maps are printed and iterated as they were built
+pub type VariableStore = IndexMap<String, Variable>;
 
-pub struct Variable {
-    value: Value,
-    string_value: String,
-    struct_value: VariableStore,
-    param_type: VariableType
+#[derive(Clone, Debug, PartialEq)]
+pub enum Variable {
+    Null,
+    Boolean(bool),
+    Float(f64),
+    Int(i128),
+    String(String),
+    List(Vec<Variable>),
+    Map(VariableStore),
+    Object(Arc<dyn TemplateObject>),
+    Lazy(Arc<LazyVariable>),
 }
 
 impl Variable {
+    /// Creates an empty string
     pub fn new() -> Variable {
-        Variable {
-            value: Value { int_value: 0 },
-            string_value: String::new(),
-            param_type: VariableType::StringType,
-            struct_value: HashMap::new(),
-        }
+        Variable::String(String::new())
+    }
+
+    pub fn new_null() -> Variable {
+        Variable::Null
     }
 
     pub fn new_from_boolean(value: bool) -> Variable {
-        let mut p = Variable::new();
-        p.set_boolean_value(value);
-        p
+        Variable::Boolean(value)
     }
 
     pub fn new_from_string(string: String) -> Variable {
-        let mut p = Variable::new();
-        p.set_string_value(string);
-        p
+        Variable::String(string)
     }
 
     pub fn new_from_str(string: &str) -> Variable {
-        let mut p = Variable::new();
-        p.set_string_value(String::from(string));
-        p
+        Variable::String(String::from(string))
     }
 
     pub fn new_from_int(value: i128) -> Variable {
-        let mut p = Variable::new();
-        p.set_int_value(value);
-        p
+        Variable::Int(value)
     }
 
     pub fn new_from_float(value: f64) -> Variable {
-        let mut p = Variable::new();
-        p.set_float_value(value);
-        p
+        Variable::Float(value)
+    }
+
+    pub fn new_from_list(value: Vec<Variable>) -> Variable {
+        Variable::List(value)
+    }
+
+    pub fn new_from_struct(value: VariableStore) -> Variable {
+        Variable::Map(value)
+    }
+
+    pub fn new_from_map(value: VariableStore) -> Variable {
+        Variable::Map(value)
+    }
+
+    pub fn new_from_object<T: TemplateObject + 'static>(object: T) -> Variable {
+        Variable::Object(Arc::new(object))
+    }
+
+    /// Creates a variable computed when it is referenced in a template
+    pub fn new_lazy<F>(function: F) -> Variable
+        where F: Fn() -> Result<Variable, EvaluationError> + Send + Sync + 'static {
+        Variable::Lazy(Arc::new(LazyVariable::new(function)))
+    }
+
+    pub fn set_null(&mut self) {
+        *self = Variable::Null;
     }
 
     pub fn set_boolean_value(&mut self, value: bool) {
-        self.param_type = VariableType::Boolean;
-        self.value = Value { boolean_value: value };
+        *self = Variable::Boolean(value);
     }
 
     pub fn set_float_value(&mut self, value: f64) {
-        self.param_type = VariableType::Float;
-        self.value = Value { float_value: value };
+        *self = Variable::Float(value);
     }
 
     pub fn set_int_value(&mut self, value: i128) {
-        self.param_type = VariableType::Int;
-        self.value = Value { int_value: value };
+        *self = Variable::Int(value);
+    }
+
+    pub fn set_list_value(&mut self, value: Vec<Variable>) {
+        *self = Variable::List(value);
     }
 
     pub fn set_string_value(&mut self, value: String) {
-        self.param_type = VariableType::StringType;
-        self.string_value = value;
+        *self = Variable::String(value);
     }
 
     pub fn set_struct_value(&mut self, value: VariableStore) {
-        self.param_type = VariableType::Struct;
-        self.struct_value = value;
+        *self = Variable::Map(value);
+    }
+
+    pub fn set_map_value(&mut self, value: VariableStore) {
+        *self = Variable::Map(value);
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Variable::Null)
     }
 
     pub fn get_string_value(&self) -> String {
-        unsafe {
-            match self.param_type {
-                VariableType::Boolean => String::from(if self.value.boolean_value == true { "true" } else { "false" }),
-                VariableType::Float => self.value.float_value.to_string(),
-                VariableType::Int => self.value.int_value.to_string(),
-                VariableType::StringType => self.string_value.clone(),
-                VariableType::Struct => panic!("Not implemented") // TODO: implement
-            }
+        match self {
+            Variable::Null => String::new(),
+            Variable::Boolean(value) => String::from(if *value { "true" } else { "false" }),
+            Variable::Float(value) => value.to_string(),
+            Variable::Int(value) => value.to_string(),
+            Variable::String(value) => value.clone(),
+            Variable::List(list) => format_list(list),
+            Variable::Map(map) => format_map(map),
+            Variable::Object(object) => object.get_string_value(),
+            // Templates resolve lazy variables beforehand, see `RenderContext::resolve_nested`.
+            // Here the closure is called each time and an error gives an empty string
+            Variable::Lazy(lazy) => lazy.evaluate().map(|v| v.get_string_value()).unwrap_or_default(),
         }
     }
 
     pub fn get_boolean_value(&self) -> bool {
-        unsafe {
-            match self.param_type {
-                VariableType::Boolean => self.value.boolean_value,
-                VariableType::Float => self.value.float_value != 0.0,
-                VariableType::Int => self.value.int_value != 0,
-                VariableType::StringType => self.string_value != "",
-                VariableType::Struct => panic!("Not implemented") // TODO: implement
-            }
+        match self {
+            Variable::Null => false,
+            Variable::Boolean(value) => *value,
+            Variable::Float(value) => *value != 0.0,
+            Variable::Int(value) => *value != 0,
+            Variable::String(value) => !value.is_empty(),
+            Variable::List(list) => !list.is_empty(),
+            Variable::Map(map) => !map.is_empty(),
+            Variable::Object(object) => object.get_boolean_value(),
+            // Lazy variables are resolved beforehand in templates, as in `get_string_value`
+            Variable::Lazy(lazy) => lazy.evaluate().map(|v| v.get_boolean_value()).unwrap_or(false),
         }
     }
 
     pub fn get_int_value(&self) -> Option<i128> {
-        unsafe {
-            match self.param_type {
-                VariableType::Int => Some(self.value.int_value),
-                _ => None,
-            }
+        match self {
+            Variable::Int(value) => Some(*value),
+            _ => None,
         }
     }
 
     pub fn get_float_value(&self) -> Option<f64> {
-        unsafe {
-            match self.param_type {
-                VariableType::Float => Some(self.value.float_value),
-                VariableType::Int => Some(self.value.int_value as f64),
-                _ => None,
-            }
+        match self {
+            Variable::Float(value) => Some(*value),
+            Variable::Int(value) => Some(*value as f64),
+            _ => None,
         }
     }
 
-    pub fn clone(&self) -> Variable {
-        let mut dest = Variable::new();
-        unsafe {
-            match self.param_type {
-                VariableType::Boolean => { dest.set_boolean_value(self.value.boolean_value); },
-                VariableType::Float => { dest.set_float_value(self.value.float_value); },
-                VariableType::Int => { dest.set_int_value(self.value.int_value); },
-                VariableType::StringType => { dest.set_string_value(self.string_value.clone()); },
-                VariableType::Struct => { dest.set_struct_value(clone_parameter_store(&self.struct_value)); }
-            }
-    
+    pub fn get_list_value(&self) -> Option<&Vec<Variable>> {
+        match self {
+            Variable::List(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    pub fn get_struct_value(&self) -> Option<&VariableStore> {
+        self.get_map_value()
+    }
+
+    pub fn get_map_value(&self) -> Option<&VariableStore> {
+        match self {
+            Variable::Map(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    pub fn get_object_value(&self) -> Option<&Arc<dyn TemplateObject>> {
+        match self {
+            Variable::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+
+    /// Returns a name of the variable type, e.g. for error messages
+    pub fn get_type_name(&self) -> &'static str {
+        match self {
+            Variable::Null => "null",
+            Variable::Boolean(_) => "boolean",
+            Variable::Float(_) => "float",
+            Variable::Int(_) => "int",
+            Variable::String(_) => "string",
+            Variable::List(_) => "list",
+            Variable::Map(_) => "map",
+            Variable::Object(_) => "object",
+            Variable::Lazy(_) => "lazy",
         }
-        dest
     }
 
     pub fn as_string(&self) -> String {
@@ -151,14 +204,190 @@ impl Variable {
     }
 }
 
-fn clone_parameter_store(src: &VariableStore) -> VariableStore {
-    let mut dest = VariableStore::new();
-    for (k, v) in src.iter() {
-        dest.insert(k.to_string(), v.clone());
+impl From<bool> for Variable {
+    fn from(value: bool) -> Self {
+        Variable::Boolean(value)
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Variable {
+            fn from(value: $t) -> Self {
+                Variable::Int(value as i128)
+            }
+        })*
+    };
+}
+
+impl_from_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<f32> for Variable {
+    fn from(value: f32) -> Self {
+        Variable::Float(value as f64)
+    }
+}
+
+impl From<f64> for Variable {
+    fn from(value: f64) -> Self {
+        Variable::Float(value)
+    }
+}
+
+impl From<&str> for Variable {
+    fn from(value: &str) -> Self {
+        Variable::String(String::from(value))
+    }
+}
+
+impl From<String> for Variable {
+    fn from(value: String) -> Self {
+        Variable::String(value)
+    }
+}
+
+impl<T: Into<Variable>> From<Vec<T>> for Variable {
+    fn from(value: Vec<T>) -> Self {
+        Variable::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Variable>> From<Option<T>> for Variable {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(v) => v.into(),
+            None => Variable::Null,
+        }
+    }
+}
+
+impl From<VariableStore> for Variable {
+    fn from(value: VariableStore) -> Self {
+        Variable::Map(value)
+    }
+}
+
+impl<T: Into<Variable>> From<HashMap<String, T>> for Variable {
+    /// Keys of a `HashMap` have no order, so they are sorted to keep the output stable
+    fn from(value: HashMap<String, T>) -> Self {
+        let mut items: Vec<(String, T)> = value.into_iter().collect();
+        items.sort_by(|a, b| a.0.cmp(&b.0));
+        Variable::Map(items.into_iter().map(|(k, v)| (k, v.into())).collect())
+    }
+}
+
+/// Conversion of a value to a variable by reference, so it can be passed to a template without giving it away.
+/// Can be derived for structs and enums with `#[derive(ToVariable)]`:
+/// named fields become map entries, the attributes `#[pete(rename = "key")]`, `#[pete(skip)]`
+/// and `#[pete(flatten)]` change how a field is converted
+pub trait ToVariable {
+    fn to_variable(&self) -> Variable;
+}
+
+impl ToVariable for Variable {
+    fn to_variable(&self) -> Variable {
+        self.clone()
+    }
+}
+
+macro_rules! impl_to_variable {
+    ($($t:ty),*) => {
+        $(impl ToVariable for $t {
+            fn to_variable(&self) -> Variable {
+                Variable::from(self.clone())
+            }
+        })*
+    };
+}
+
+impl_to_variable!(bool, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize, f32, f64, String);
+
+impl ToVariable for str {
+    fn to_variable(&self) -> Variable {
+        Variable::from(self)
+    }
+}
+
+impl ToVariable for char {
+    fn to_variable(&self) -> Variable {
+        Variable::from(self.to_string())
     }
-    dest
 }
 
+impl<T: ToVariable + ?Sized> ToVariable for &T {
+    fn to_variable(&self) -> Variable {
+        (**self).to_variable()
+    }
+}
+
+impl<T: ToVariable + ?Sized> ToVariable for Box<T> {
+    fn to_variable(&self) -> Variable {
+        (**self).to_variable()
+    }
+}
+
+impl<T: ToVariable> ToVariable for Option<T> {
+    fn to_variable(&self) -> Variable {
+        match self {
+            Some(value) => value.to_variable(),
+            None => Variable::Null,
+        }
+    }
+}
+
+impl<T: ToVariable> ToVariable for [T] {
+    fn to_variable(&self) -> Variable {
+        Variable::List(self.iter().map(ToVariable::to_variable).collect())
+    }
+}
+
+impl<T: ToVariable> ToVariable for Vec<T> {
+    fn to_variable(&self) -> Variable {
+        self.as_slice().to_variable()
+    }
+}
+
+impl<T: ToVariable> ToVariable for IndexMap<String, T> {
+    fn to_variable(&self) -> Variable {
+        Variable::Map(self.iter().map(|(k, v)| (k.clone(), v.to_variable())).collect())
+    }
+}
+
+impl<T: ToVariable> ToVariable for BTreeMap<String, T> {
+    fn to_variable(&self) -> Variable {
+        Variable::Map(self.iter().map(|(k, v)| (k.clone(), v.to_variable())).collect())
+    }
+}
+
+impl<T: ToVariable> ToVariable for HashMap<String, T> {
+    /// Keys of a `HashMap` have no order, so they are sorted to keep the output stable
+    fn to_variable(&self) -> Variable {
+        let mut keys: Vec<&String> = self.keys().collect();
+        keys.sort();
+        Variable::Map(keys.into_iter().map(|k| (k.clone(), self[k].to_variable())).collect())
+    }
+}
+
+// Formats a list as `[1, "a", true]`
+fn format_list(list: &[Variable]) -> String {
+    let items: Vec<String> = list.iter().map(format_item).collect();
+    format!("[{}]", items.join(", "))
+}
+
+// Formats a map as `{"city": "Paris", "zip": 75001}`, keys are printed in insertion order
+fn format_map(map: &VariableStore) -> String {
+    let items: Vec<String> = map.iter().map(|(k, v)| format!("{:?}: {}", k, format_item(v))).collect();
+    format!("{{{}}}", items.join(", "))
+}
+
+// Strings inside of lists and maps are quoted, null is printed as `null`
+fn format_item(value: &Variable) -> String {
+    match value {
+        Variable::String(string) => format!("{:?}", string),
+        Variable::Null => String::from("null"),
+        _ => value.get_string_value(),
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -187,4 +416,75 @@ mod tests {
         let value = Variable::new_from_int(0);
         assert_eq!(value.get_boolean_value(), false);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_common_variable_struct() {
+        let mut address = VariableStore::new();
+        address.insert(String::from("zip"), Variable::new_from_int(75001));
+        address.insert(String::from("city"), Variable::new_from_str("Paris"));
+        let mut user = VariableStore::new();
+        user.insert(String::from("address"), Variable::new_from_struct(address));
+        let value = Variable::new_from_struct(user);
+        assert!(value.get_boolean_value());
+        assert_eq!(value.get_type_name(), "map");
+        assert_eq!(value.get_string_value(), "{\"address\": {\"zip\": 75001, \"city\": \"Paris\"}}");
+        assert_eq!(value.get_struct_value().map(|s| s.len()), Some(1));
+
+        let value = Variable::new_from_struct(VariableStore::new());
+        assert!(!value.get_boolean_value());
+        assert_eq!(value.get_string_value(), "{}");
+    }
+
+    #[test]
+    fn test_common_variable_list() {
+        let value = Variable::new_from_list(vec![
+            Variable::new_from_int(1),
+            Variable::new_from_str("a"),
+            Variable::new_from_list(vec![Variable::new_from_boolean(true)]),
+        ]);
+        assert!(value.get_boolean_value());
+        assert_eq!(value.get_type_name(), "list");
+        assert_eq!(value.get_string_value(), "[1, \"a\", [true]]");
+        assert_eq!(value.clone().get_list_value().map(|l| l.len()), Some(3));
+        assert!(!Variable::new_from_list(Vec::new()).get_boolean_value());
+    }
+
+    #[test]
+    fn test_common_variable_null() {
+        let value = Variable::new_null();
+        assert!(value.is_null());
+        assert!(!value.get_boolean_value());
+        assert_eq!(value.get_type_name(), "null");
+        assert_eq!(value.get_string_value(), "");
+        assert_eq!(Variable::new_from_list(vec![Variable::Null]).get_string_value(), "[null]");
+    }
+
+    #[test]
+    fn test_common_variable_from() {
+        assert_eq!(Variable::from(true), Variable::Boolean(true));
+        assert_eq!(Variable::from(42u8), Variable::Int(42));
+        assert_eq!(Variable::from(-7i64), Variable::Int(-7));
+        assert_eq!(Variable::from(2.5), Variable::Float(2.5));
+        assert_eq!(Variable::from("a"), Variable::new_from_str("a"));
+        assert_eq!(Variable::from(vec![1, 2]), Variable::new_from_list(vec![Variable::Int(1), Variable::Int(2)]));
+        assert_eq!(Variable::from(None::<i32>), Variable::Null);
+        assert_eq!(Variable::from(Some("x")), Variable::new_from_str("x"));
+
+        let mut map = HashMap::new();
+        map.insert(String::from("b"), 2);
+        map.insert(String::from("a"), 1);
+        assert_eq!(Variable::from(map).get_string_value(), "{\"a\": 1, \"b\": 2}");
+    }
+
+    #[test]
+    fn test_common_variable_to_variable() {
+        assert_eq!(42u16.to_variable(), Variable::Int(42));
+        assert_eq!("a".to_variable(), Variable::new_from_str("a"));
+        assert_eq!(vec![Some(1), None].to_variable().get_string_value(), "[1, null]");
+
+        let mut map = HashMap::new();
+        map.insert(String::from("b"), vec!['x']);
+        map.insert(String::from("a"), Vec::new());
+        assert_eq!(map.to_variable().get_string_value(), "{\"a\": [], \"b\": [\"x\"]}");
+    }
+}
@@ -1,5 +1,11 @@
+use std::sync::Arc;
+
+use crate::context::parsing_context::ParsingContext;
+
 pub struct BuildContext {
     pub offset: usize,
+    /// Shared with the engine, so building an expression does not copy the filters and functions
+    pub parsing_context: Arc<ParsingContext>,
     pub template: String,
     pub template_remain: String,
 }
@@ -8,6 +14,7 @@ impl BuildContext {
     pub fn new() -> BuildContext {
         BuildContext {
             offset: 0,
+            parsing_context: Arc::new(ParsingContext::new()),
             template: String::new(),
             template_remain: String::new(),
         }
@@ -16,6 +23,7 @@ impl BuildContext {
     pub fn clone(&self) -> BuildContext {
         BuildContext {
             offset: self.offset,
+            parsing_context: self.parsing_context.clone(),
             template: self.template.clone(),
             template_remain: self.template.clone(),
         }
@@ -26,4 +34,16 @@ impl BuildContext {
         self.template_remain = self.template_remain[offset+1..].to_string();
         self.offset += offset;
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_build_context_clone() {
+        let context = BuildContext::new();
+        let cloned = context.clone();
+        assert!(Arc::ptr_eq(&context.parsing_context, &cloned.parsing_context));
+    }
 }
\ No newline at end of file
@@ -1,2 +1,4 @@
 pub mod build_context;
-pub mod render_context;
\ No newline at end of file
+pub mod parsing_context;
+pub mod render_context;
+pub mod scope_view;
\ No newline at end of file
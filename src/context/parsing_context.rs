@@ -0,0 +1,32 @@
+use crate::expressions::filters::FilterStore;
+use crate::expressions::functions::FunctionStore;
+
+/// Data available while an expression is parsed, e.g. registered functions and filters
+pub struct ParsingContext {
+    pub filters: FilterStore,
+    pub functions: FunctionStore,
+}
+
+impl Clone for ParsingContext {
+    fn clone(&self) -> ParsingContext {
+        ParsingContext {
+            filters: self.filters.clone(),
+            functions: self.functions.clone(),
+        }
+    }
+}
+
+impl ParsingContext {
+    pub fn new() -> ParsingContext {
+        ParsingContext {
+            filters: FilterStore::new(),
+            functions: FunctionStore::new(),
+        }
+    }
+}
+
+impl Default for ParsingContext {
+    fn default() -> ParsingContext {
+        ParsingContext::new()
+    }
+}
@@ -1,31 +1,276 @@
-use crate::common::variable::VariableStore;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
 
+use crate::common::lazy_variable::LazyVariable;
+use crate::common::variable::{Variable, VariableStore};
+use crate::context::parsing_context::ParsingContext;
+use crate::context::scope_view::ScopeView;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::loaders::{TemplateCache, TemplateLoader};
+
+// Values of lazy variables by addresses of the lazy variables
+type LazyValues = HashMap<usize, (Arc<LazyVariable>, Variable)>;
+
+/// Defines how a reference to an undefined variable, attribute or item is rendered
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UndefinedPolicy {
+    /// An error stops the render. `??` and the `default` filter still work
+    Strict,
+    /// Undefined values are null, so they are printed as empty strings.
+    /// Getting an attribute of an undefined value is an error
+    Lenient,
+    /// Like `Lenient`, but attributes and items of undefined values are undefined as well: `missing.name`
+    Chainable,
+}
+
+/// Variables are looked up in scopes pushed by tags (e.g. loops) from the innermost one,
+/// then in `parameters`, the variables of the template, then in `globals` of the engine.
+/// Scopes and parameters are shared by clones of a context until one of them changes a scope
 pub struct RenderContext {
     pub filename: String,
+    pub globals: Arc<VariableStore>,
+    /// Number of includes the template is nested in
+    pub include_depth: usize,
+    pub loader: Option<Arc<dyn TemplateLoader>>,
     pub offset: usize,
-    pub parameters: VariableStore,
+    pub parameters: Arc<VariableStore>,
+    /// Filters and functions of the engine, used to build included templates
+    pub parsing_context: Arc<ParsingContext>,
     pub template: String,
+    /// Templates built by the loader, shared by all renders of the engine
+    pub template_cache: Arc<TemplateCache>,
+    pub undefined_policy: UndefinedPolicy,
+    scopes: Vec<Arc<VariableStore>>,
+    // Values of lazy variables computed during the render, by addresses of the lazy variables.
+    // The lazy variable is kept alongside its value, so its address cannot be reused by another one.
+    // Shared with contexts of included templates
+    lazy_values: Rc<RefCell<LazyValues>>,
 }
 
 impl RenderContext {
     pub fn new() -> RenderContext {
         RenderContext {
             filename: String::new(),
+            globals: Arc::new(VariableStore::new()),
+            include_depth: 0,
+            loader: None,
             offset: 0,
-            parameters: VariableStore::new(),
+            parameters: Arc::new(VariableStore::new()),
+            parsing_context: Arc::new(ParsingContext::new()),
             template: String::new(),
+            template_cache: Arc::new(TemplateCache::new()),
+            undefined_policy: UndefinedPolicy::Strict,
+            scopes: Vec::new(),
+            lazy_values: Rc::new(RefCell::new(HashMap::new())),
         }
     }
 
     pub fn clone(&self) -> RenderContext {
         let mut cloned = RenderContext::new();
         cloned.filename = self.filename.clone();
+        cloned.globals = self.globals.clone();
+        cloned.include_depth = self.include_depth;
+        cloned.loader = self.loader.clone();
         cloned.offset = self.offset;
-        for (k, v) in self.parameters.iter() {
-            cloned.parameters.insert(k.to_string(), v.clone());
-        }
+        cloned.parameters = self.parameters.clone();
+        cloned.parsing_context = self.parsing_context.clone();
         cloned.template = self.template.clone();
+        cloned.template_cache = self.template_cache.clone();
+        cloned.undefined_policy = self.undefined_policy;
+        cloned.scopes = self.scopes.clone();
+        cloned.lazy_values = self.lazy_values.clone();
 
         cloned
     }
-}
\ No newline at end of file
+
+    /// Starts a scope, e.g. of a loop. Variables assigned with `set_local` are removed with the scope
+    pub fn push_scope(&mut self) {
+        self.scopes.push(Arc::new(VariableStore::new()));
+    }
+
+    pub fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Returns a variable from the innermost scope which has it
+    pub fn get_variable(&self, name: &str) -> Option<&Variable> {
+        self.scopes.iter().rev()
+            .find_map(|scope| scope.get(name))
+            .or_else(|| self.parameters.get(name))
+            .or_else(|| self.globals.get(name))
+    }
+
+    /// Returns all visible variables, an inner variable hides outer variables with the same name
+    pub fn get_variables(&self) -> VariableStore {
+        self.get_scope_view().get_variables()
+    }
+
+    /// Returns a view of the variables visible now, e.g. `loop.parent`.
+    /// Scopes pushed later are not part of it, and variables changed later keep their old values in it
+    pub fn get_scope_view(&self) -> ScopeView {
+        let mut frames = vec![self.globals.clone(), self.parameters.clone()];
+        frames.extend(self.scopes.iter().cloned());
+        ScopeView::new(frames)
+    }
+
+    /// Returns visible variables except globals
+    pub fn get_template_variables(&self) -> VariableStore {
+        let mut variables = (*self.parameters).clone();
+        for scope in &self.scopes {
+            for (name, value) in scope.iter() {
+                variables.insert(name.clone(), value.clone());
+            }
+        }
+        variables
+    }
+
+    /// Creates a context for rendering an included template with the settings of this one
+    pub fn create_include_context(&self, filename: &str, template: String, parameters: VariableStore) -> RenderContext {
+        let mut context = RenderContext::new();
+        context.filename = String::from(filename);
+        context.globals = self.globals.clone();
+        context.include_depth = self.include_depth + 1;
+        context.loader = self.loader.clone();
+        context.parameters = Arc::new(parameters);
+        context.parsing_context = self.parsing_context.clone();
+        context.template = template;
+        context.template_cache = self.template_cache.clone();
+        context.undefined_policy = self.undefined_policy;
+        context.lazy_values = self.lazy_values.clone();
+        context
+    }
+
+    /// Assigns a variable in the innermost scope, e.g. a loop variable
+    pub fn set_local(&mut self, name: &str, value: Variable) {
+        let scope = match self.scopes.last_mut() {
+            Some(scope) => scope,
+            None => &mut self.parameters,
+        };
+        Arc::make_mut(scope).insert(String::from(name), value);
+    }
+
+    /// Assigns a variable of the template, like variables passed to `Engine::render`
+    pub fn set_parameter(&mut self, name: &str, value: Variable) {
+        Arc::make_mut(&mut self.parameters).insert(String::from(name), value);
+    }
+
+    /// Assigns a variable as `{% set %}` does: a variable of an outer scope is updated,
+    /// a new variable is added to the innermost scope. Globals are hidden, not changed
+    pub fn set_variable(&mut self, name: &str, value: Variable) {
+        let scope = match self.scopes.iter_mut().rev().find(|scope| scope.contains_key(name)) {
+            Some(scope) => scope,
+            None if self.parameters.contains_key(name) => &mut self.parameters,
+            None => return self.set_local(name, value),
+        };
+        Arc::make_mut(scope).insert(String::from(name), value);
+    }
+
+    /// Returns null for an undefined value in lenient modes or an error in the strict mode
+    pub fn get_undefined(&self, offset: Option<usize>, message: String) -> Result<Variable, EvaluationError> {
+        match self.undefined_policy {
+            UndefinedPolicy::Strict => Err(EvaluationError::new_undefined(offset, message)),
+            UndefinedPolicy::Lenient | UndefinedPolicy::Chainable => Ok(Variable::Null),
+        }
+    }
+
+    /// Computes a lazy variable, or returns its value if it has been computed during this render.
+    /// Other variables are returned unchanged
+    pub fn resolve(&self, variable: Variable) -> Result<Variable, EvaluationError> {
+        let lazy = match variable {
+            Variable::Lazy(lazy) => lazy,
+            _ => return Ok(variable),
+        };
+        let address = Arc::as_ptr(&lazy) as usize;
+        if let Some((_, value)) = self.lazy_values.borrow().get(&address) {
+            return Ok(value.clone());
+        }
+        // A lazy variable may return another lazy variable
+        let value = self.resolve(lazy.evaluate()?)?;
+        self.lazy_values.borrow_mut().insert(address, (lazy, value.clone()));
+        Ok(value)
+    }
+
+    /// Resolves a variable and lazy variables in its lists and maps, e.g. before it is printed or passed to a filter
+    pub fn resolve_nested(&self, variable: Variable) -> Result<Variable, EvaluationError> {
+        match self.resolve(variable)? {
+            Variable::List(list) => list.into_iter()
+                .map(|item| self.resolve_nested(item))
+                .collect::<Result<Vec<Variable>, EvaluationError>>()
+                .map(Variable::List),
+            Variable::Map(map) => map.into_iter()
+                .map(|(key, value)| Ok((key, self.resolve_nested(value)?)))
+                .collect::<Result<VariableStore, EvaluationError>>()
+                .map(Variable::Map),
+            variable => Ok(variable),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_render_context_scopes() {
+        let mut globals = VariableStore::new();
+        globals.insert(String::from("site"), Variable::from("Blog"));
+        globals.insert(String::from("total"), Variable::from(0));
+        let mut context = RenderContext::new();
+        context.globals = Arc::new(globals);
+        context.set_parameter("total", Variable::from(1));
+
+        context.push_scope();
+        context.set_local("item", Variable::from("a"));
+        context.set_variable("total", Variable::from(2));
+        context.set_variable("site", Variable::from("Shop"));
+        context.set_variable("count", Variable::from(3));
+        assert_eq!(context.get_variable("item"), Some(&Variable::from("a")));
+        assert_eq!(context.get_variable("count"), Some(&Variable::from(3)));
+        assert_eq!(context.get_variables().keys().collect::<Vec<_>>(), vec!["site", "total", "item", "count"]);
+        context.pop_scope();
+
+        // Updated outer variables are kept, new variables are removed with the scope
+        assert_eq!(context.get_variable("total"), Some(&Variable::from(2)));
+        assert_eq!(context.get_variable("item"), None);
+        assert_eq!(context.get_variable("count"), None);
+        assert_eq!(context.get_variable("site"), Some(&Variable::from("Blog")));
+        assert_eq!(context.globals.get("total"), Some(&Variable::from(0)));
+    }
+
+    #[test]
+    fn test_context_render_context_clone() {
+        let mut context = RenderContext::new();
+        context.set_parameter("total", Variable::from(1));
+        context.push_scope();
+        context.set_local("item", Variable::from("a"));
+
+        // Frames are shared until a clone changes them
+        let mut cloned = context.clone();
+        assert!(Arc::ptr_eq(&context.parameters, &cloned.parameters));
+        assert!(Arc::ptr_eq(&context.scopes[0], &cloned.scopes[0]));
+        cloned.set_variable("item", Variable::from("b"));
+        assert!(Arc::ptr_eq(&context.parameters, &cloned.parameters));
+        assert_eq!(context.get_variable("item"), Some(&Variable::from("a")));
+        assert_eq!(cloned.get_variable("item"), Some(&Variable::from("b")));
+    }
+
+    #[test]
+    fn test_context_render_context_scope_view() {
+        let mut context = RenderContext::new();
+        context.set_parameter("total", Variable::from(1));
+        context.push_scope();
+        context.set_local("row", Variable::from(1));
+        let view = context.get_scope_view();
+        context.push_scope();
+        context.set_local("cell", Variable::from(2));
+        context.set_variable("total", Variable::from(3));
+
+        // The view keeps the outer variables with the values they had when it was created
+        let variables = view.get_variables();
+        assert_eq!(variables.keys().collect::<Vec<_>>(), vec!["total", "row"]);
+        assert_eq!(variables.get("total"), Some(&Variable::from(1)));
+        assert_eq!(context.get_variable("total"), Some(&Variable::from(3)));
+    }
+}
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use crate::common::template_object::TemplateObject;
+use crate::common::variable::{Variable, VariableStore};
+
+/// Variables visible in a render context when the view is created, e.g. `loop.parent`.
+/// The view shares scopes with the context, so nothing is copied unless the context changes them later
+pub struct ScopeView {
+    // From the globals to the innermost scope
+    frames: Vec<Arc<VariableStore>>,
+}
+
+impl ScopeView {
+    pub fn new(frames: Vec<Arc<VariableStore>>) -> ScopeView {
+        ScopeView {
+            frames,
+        }
+    }
+
+    /// Returns all variables of the view, an inner variable hides outer variables with the same name
+    pub fn get_variables(&self) -> VariableStore {
+        let mut variables = VariableStore::new();
+        for frame in &self.frames {
+            variables.extend(frame.iter().map(|(name, value)| (name.clone(), value.clone())));
+        }
+        variables
+    }
+}
+
+impl TemplateObject for ScopeView {
+    fn get_attribute(&self, name: &str) -> Option<Variable> {
+        self.frames.iter().rev().find_map(|frame| frame.get(name)).cloned()
+    }
+
+    fn get_string_value(&self) -> String {
+        Variable::Map(self.get_variables()).get_string_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_scope_view() {
+        let mut outer = VariableStore::new();
+        outer.insert(String::from("total"), Variable::from(1));
+        outer.insert(String::from("site"), Variable::from("Blog"));
+        let mut inner = VariableStore::new();
+        inner.insert(String::from("total"), Variable::from(2));
+        let view = ScopeView::new(vec![Arc::new(outer), Arc::new(inner)]);
+
+        assert_eq!(view.get_attribute("total"), Some(Variable::from(2)));
+        assert_eq!(view.get_attribute("site"), Some(Variable::from("Blog")));
+        assert_eq!(view.get_attribute("missing"), None);
+        assert_eq!(view.get_string_value(), "{\"total\": 2, \"site\": \"Blog\"}");
+    }
+}
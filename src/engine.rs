@@ -1,8 +1,16 @@
+use std::sync::Arc;
+
 use crate::error::template_error::TemplateError;
+use crate::expressions::filters::{self, Filter};
+use crate::expressions::functions::{self, Function};
 
-use crate::common::variable::VariableStore;
+#[cfg(feature = "serde")]
+use crate::common::serializer;
+use crate::common::variable::{Variable, VariableStore};
 use crate::context::build_context::BuildContext;
-use crate::context::render_context::RenderContext;
+use crate::context::parsing_context::ParsingContext;
+use crate::context::render_context::{RenderContext, UndefinedPolicy};
+use crate::loaders::{TemplateCache, TemplateLoader};
 use crate::nodes::Node;
 use crate::nodes::NodeCreator;
 use crate::nodes::container::ContainerNode;
@@ -10,6 +18,7 @@ use crate::nodes::comment::CommentNode;
 use crate::nodes::expression::ExpressionNode;
 use crate::nodes::static_node::StaticNode;
 use crate::nodes::tags;
+use crate::nodes::tags::include::{self, IncludeError};
 
 const NODE_CREATORS: [NodeCreator; 4] = [
     CommentNode::try_create_from_template,
@@ -18,7 +27,13 @@ const NODE_CREATORS: [NodeCreator; 4] = [
     tags::try_create_from_template,
 ];
 
-pub struct Engine {}
+pub struct Engine {
+    globals: Arc<VariableStore>,
+    loader: Option<Arc<dyn TemplateLoader>>,
+    parsing_context: Arc<ParsingContext>,
+    template_cache: Arc<TemplateCache>,
+    undefined_policy: UndefinedPolicy,
+}
 
 pub struct NodeBuildData {
     // end position of node. Relative to start of node.
@@ -44,109 +59,189 @@ pub type RenderResult = Result<String, TemplateError>;
 
 impl Engine {
     pub fn new() -> Engine {
-        Engine {}
+        let mut parsing_context = ParsingContext::new();
+        parsing_context.filters = filters::get_builtin_filters();
+        parsing_context.functions = functions::get_builtin_functions();
+        Engine {
+            globals: Arc::new(VariableStore::new()),
+            loader: None,
+            parsing_context: Arc::new(parsing_context),
+            template_cache: Arc::new(TemplateCache::new()),
+            undefined_policy: UndefinedPolicy::Strict,
+        }
     }
 
-    fn parse_node(&self, build_context: &BuildContext) -> Option<Box<dyn Node>> {
-        for node_creator in NODE_CREATORS.iter() {
-            let parsed_node = node_creator(&build_context.template_remain);
-            if parsed_node.is_some() {
-                return parsed_node;
-            }
-        }
-        None
+    /// Defines how undefined variables, attributes and items are rendered. `UndefinedPolicy::Strict` by default
+    pub fn set_undefined_policy(&mut self, policy: UndefinedPolicy) {
+        self.undefined_policy = policy;
     }
 
-    fn build_continuation(&self, build_context: &mut BuildContext,
-                          nodes_stack: &mut Vec<Box<dyn Node>>, mut parent_node: Box<dyn Node>)
-                          -> Result<Box<dyn Node>, TemplateError> {
-        let data = parent_node.build(&build_context)?;
-        let node = if data.is_nesting_started {
-            parent_node
-        } else {
-            match nodes_stack.pop() {
-                Some(mut upper_parent_node) => {
-                    upper_parent_node.add_child(parent_node);
-                    
-                    Ok(upper_parent_node)
-                },
-                None => Err(TemplateError::create(
-                        build_context.template.clone(),
-                        build_context.offset,
-                        String::from("Unexpected end of node stack.")))
-            }?
-        };
-        build_context.apply_offset(data.end_offset);
-        Ok(node)
+    /// Adds a variable available in all templates, e.g. `{{ site_name }}`.
+    /// Variables passed to `render` hide globals with the same names
+    pub fn add_global(&mut self, name: &str, value: Variable) {
+        Arc::make_mut(&mut self.globals).insert(String::from(name), value);
     }
 
-    fn build_new_block(&self, build_context: &mut BuildContext,
-                       nodes_stack: &mut Vec<Box<dyn Node>>, mut parent_node: Box<dyn Node>)
-                       -> Result<Box<dyn Node>, TemplateError> {
-        let mut parsed_node = match self.parse_node(&build_context) {
-            Some(n) => Ok(n),
-            None => Err(TemplateError::create(
-                build_context.template.clone(),
-                build_context.offset,
-                String::from("Cannot recognize a node"))),
-        }?;
-        let data = parsed_node.build(&build_context)?;
-        let node = if data.is_nesting_started {
-            nodes_stack.push(parent_node);
-            build_context.apply_offset(data.end_offset);
-            parsed_node
-        } else {
-            parent_node.add_child(parsed_node);
-            build_context.apply_offset(data.end_offset);
-            parent_node
-        };
-        Ok(node)
+    /// Sets the loader of templates rendered with `render_name` and included with `{% include %}` and `include()`.
+    /// Templates built with the previous loader are dropped
+    pub fn set_loader<L: TemplateLoader + 'static>(&mut self, loader: L) {
+        self.loader = Some(Arc::new(loader));
+        self.template_cache = Arc::new(TemplateCache::new());
     }
 
-    fn build(&self, template: &String) -> Result<Box<dyn Node>, TemplateError> {
-        let mut nodes_stack: Vec<Box<dyn Node>> = Vec::new();
-        let mut parent_node:Box<dyn Node> = Box::from(ContainerNode::create());
-        let mut build_context = BuildContext::new();
-        build_context.template = template.clone();
-        build_context.template_remain = template.clone();
-        let mut prev_template_remain_len = build_context.template_remain.len()+1;
-        while build_context.template_remain.len() > 0 {
-            if build_context.template_remain.len() >= prev_template_remain_len {
-                panic!("An infinite loop detected.")
-            }
-            prev_template_remain_len = build_context.template_remain.len();
+    /// Registers a function callable from templates, e.g. `{{ price(item, currency="EUR") }}`.
+    /// A function with the same name is replaced. Templates built before are dropped
+    pub fn add_function(&mut self, name: &str, function: Function) {
+        Arc::make_mut(&mut self.parsing_context).functions.insert(String::from(name), function);
+        self.template_cache = Arc::new(TemplateCache::new());
+    }
 
-            let build_result = if parent_node.is_continuation(&build_context) {
-                self.build_continuation(&mut build_context, &mut nodes_stack, parent_node)?
-            } else {
-                self.build_new_block(&mut build_context, &mut nodes_stack, parent_node)?
-            };
+    /// Registers a filter applicable in templates, e.g. `{{ price|currency("EUR") }}`.
+    /// A filter with the same name is replaced. Templates built before are dropped
+    pub fn add_filter(&mut self, name: &str, filter: Filter) {
+        Arc::make_mut(&mut self.parsing_context).filters.insert(String::from(name), filter);
+        self.template_cache = Arc::new(TemplateCache::new());
+    }
 
-            parent_node = build_result;
-            build_context.offset += 1;
-        }
-        parent_node.update_end_offset();
-        Ok(parent_node)
-    } 
+    fn build(&self, template: &String) -> Result<Box<dyn Node>, TemplateError> {
+        build_template(template, &self.parsing_context)
+    }
 
-    pub fn render(&self, template: String, parameters: VariableStore) -> RenderResult {
-        let parent_node = self.build(&template)?;
+    fn create_render_context(&self, template: String, parameters: VariableStore) -> RenderContext {
         let mut render_context = RenderContext::new();
         render_context.filename = String::from("(root)");
+        render_context.globals = self.globals.clone();
+        render_context.loader = self.loader.clone();
+        render_context.parsing_context = self.parsing_context.clone();
         render_context.template = template;
-        render_context.parameters = parameters;
+        render_context.template_cache = self.template_cache.clone();
+        render_context.parameters = Arc::new(parameters);
+        render_context.undefined_policy = self.undefined_policy;
+        render_context
+    }
+
+    pub fn render(&self, template: String, parameters: VariableStore) -> RenderResult {
+        let parent_node = self.build(&template)?;
+        let mut render_context = self.create_render_context(template, parameters);
         parent_node.render(&mut render_context)
     }
 
+    /// Renders a template found by the loader, e.g. `engine.render_name("emails/welcome.twig", variables)`.
+    /// The template is built once and cached until it is modified
+    pub fn render_name(&self, name: &str, parameters: VariableStore) -> RenderResult {
+        let render_context = self.create_render_context(String::new(), VariableStore::new());
+        match include::render_include(&render_context, name, parameters, false) {
+            Ok(result) => Ok(result),
+            Err(IncludeError::Load(message)) => Err(TemplateError::create(String::new(), 0, message)),
+            Err(IncludeError::Template(e)) => Err(e),
+        }
+    }
+
+    /// Renders a template with variables taken from a serializable struct or map, e.g. a DTO
+    /// deriving `serde::Serialize`. Its fields become variables
+    #[cfg(feature = "serde")]
+    pub fn render_serialize<T: serde::Serialize + ?Sized>(&self, template: String, context: &T) -> RenderResult {
+        match serializer::to_variable_store(context) {
+            Ok(parameters) => self.render(template, parameters),
+            Err(e) => Err(TemplateError::create(template, 0, format!("Failed to serialize the context: {}", e))),
+        }
+    }
+
     pub fn debug_print_structure(&self, template: String) -> RenderResult {
         let parent_node = self.build(&template)?;
         RenderResult::Ok(parent_node.debug_print_structure(0))
     }
 }
 
+fn parse_node(build_context: &BuildContext) -> Option<Box<dyn Node>> {
+    for node_creator in NODE_CREATORS.iter() {
+        let parsed_node = node_creator(&build_context.template_remain);
+        if parsed_node.is_some() {
+            return parsed_node;
+        }
+    }
+    None
+}
+
+fn build_continuation(build_context: &mut BuildContext,
+                      nodes_stack: &mut Vec<Box<dyn Node>>, mut parent_node: Box<dyn Node>)
+                      -> Result<Box<dyn Node>, TemplateError> {
+    let data = parent_node.build(&build_context)?;
+    let node = if data.is_nesting_started {
+        parent_node
+    } else {
+        match nodes_stack.pop() {
+            Some(mut upper_parent_node) => {
+                upper_parent_node.add_child(parent_node);
+                
+                Ok(upper_parent_node)
+            },
+            None => Err(TemplateError::create(
+                    build_context.template.clone(),
+                    build_context.offset,
+                    String::from("Unexpected end of node stack.")))
+        }?
+    };
+    build_context.apply_offset(data.end_offset);
+    Ok(node)
+}
+
+fn build_new_block(build_context: &mut BuildContext,
+                   nodes_stack: &mut Vec<Box<dyn Node>>, mut parent_node: Box<dyn Node>)
+                   -> Result<Box<dyn Node>, TemplateError> {
+    let mut parsed_node = match parse_node(build_context) {
+        Some(n) => Ok(n),
+        None => Err(TemplateError::create(
+            build_context.template.clone(),
+            build_context.offset,
+            String::from("Cannot recognize a node"))),
+    }?;
+    let data = parsed_node.build(&build_context)?;
+    let node = if data.is_nesting_started {
+        nodes_stack.push(parent_node);
+        build_context.apply_offset(data.end_offset);
+        parsed_node
+    } else {
+        parent_node.add_child(parsed_node);
+        build_context.apply_offset(data.end_offset);
+        parent_node
+    };
+    Ok(node)
+}
+
+/// Builds a tree of nodes from a template
+pub fn build_template(template: &str, parsing_context: &Arc<ParsingContext>) -> Result<Box<dyn Node>, TemplateError> {
+    let mut nodes_stack: Vec<Box<dyn Node>> = Vec::new();
+    let mut parent_node:Box<dyn Node> = Box::from(ContainerNode::create());
+    let mut build_context = BuildContext::new();
+    build_context.parsing_context = parsing_context.clone();
+    build_context.template = String::from(template);
+    build_context.template_remain = String::from(template);
+    let mut prev_template_remain_len = build_context.template_remain.len()+1;
+    while build_context.template_remain.len() > 0 {
+        if build_context.template_remain.len() >= prev_template_remain_len {
+            panic!("An infinite loop detected.")
+        }
+        prev_template_remain_len = build_context.template_remain.len();
+
+        let build_result = if parent_node.is_continuation(&build_context) {
+            build_continuation(&mut build_context, &mut nodes_stack, parent_node)?
+        } else {
+            build_new_block(&mut build_context, &mut nodes_stack, parent_node)?
+        };
+
+        parent_node = build_result;
+        build_context.offset += 1;
+    }
+    parent_node.update_end_offset();
+    Ok(parent_node)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::expressions::errors::evaluation_error::EvaluationError;
+    use crate::expressions::functions::NamedArgs;
 
     #[test]
     fn test_engine_render_static_only() {
@@ -273,4 +368,300 @@ mod tests {
             }
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_engine_render_expression_no_line_breaks() {
+        let engine = Engine::new();
+        let result = engine.render(
+            String::from("Hello,\n{{- (2 + 3) * 2 -}}\n!"),
+            VariableStore::new());
+        match result {
+            Err(e) => { panic!("Failed to render a template: {}", e) },
+            Ok(result) => {
+                assert_eq!(result, "Hello,10!");
+            }
+        }
+    }
+
+    #[test]
+    fn test_engine_render_expression_unbalanced_bracket() {
+        let engine = Engine::new();
+        let result = engine.render(
+            String::from("Hello, {{ 2 * (3 + 4 }}!"),
+            VariableStore::new());
+        match result {
+            Err(e) => {
+                assert_eq!(e.offset, 14);
+                assert_eq!(e.message, "Failed to build an expression: Bracket is not closed");
+            },
+            Ok(_) => {
+                panic!("Rendering must have failed.");
+            }
+        }
+    }
+
+    #[test]
+    fn test_engine_render_string_with_closing_tag() {
+        let engine = Engine::new();
+        let mut variables = VariableStore::new();
+        variables.insert(String::from("user"), Variable::new_from_str("John"));
+        let result = engine.render(
+            String::from("{{ \"}} #{user} {{\" }}{% if 'it\\'s %}' %} ok{% endif %}"),
+            variables);
+        match result {
+            Err(e) => { panic!("Failed to render a template: {}", e) },
+            Ok(result) => {
+                assert_eq!(result, "}} John {{ ok");
+            }
+        }
+    }
+
+    #[test]
+    fn test_engine_render_function_call() {
+        fn greet(args: &[Variable], named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+            let greeting = match named_args.get("greeting") {
+                Some(g) => g.get_string_value(),
+                None => String::from("Hello"),
+            };
+            Ok(Variable::new_from_string(format!("{}, {}!", greeting, args[0].get_string_value())))
+        }
+
+        let mut engine = Engine::new();
+        engine.add_function("greet", greet);
+        let result = engine.render(
+            String::from("{{ greet('John') }} {{ greet(name, greeting='Hi') }} {{ max(1, 5, 3) }}"),
+            [(String::from("name"), Variable::new_from_str("Jane"))].iter().cloned().collect());
+        match result {
+            Err(e) => { panic!("Failed to render a template: {}", e) },
+            Ok(result) => {
+                assert_eq!(result, "Hello, John! Hi, Jane! 5");
+            }
+        }
+    }
+
+    #[test]
+    fn test_engine_render_unknown_function() {
+        let engine = Engine::new();
+        let result = engine.render(String::from("Total: {{ 1 + total(items) }}"), VariableStore::new());
+        match result {
+            Err(e) => {
+                assert_eq!(e.message, "Failed to build an expression: Unknown function \"total\"");
+                assert_eq!(e.offset, 14);
+            },
+            Ok(_) => panic!("Rendering must have failed."),
+        }
+    }
+
+    #[test]
+    fn test_engine_render_filters() {
+        fn repeat(value: &Variable, args: &[Variable], _named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+            let times = args.first().and_then(|a| a.get_int_value()).unwrap_or(2);
+            Ok(Variable::new_from_string(value.get_string_value().repeat(times as usize)))
+        }
+
+        let mut engine = Engine::new();
+        engine.add_filter("repeat", repeat);
+        let result = engine.render(
+            String::from("{{ name|trim|upper }} {{ 'ab'|repeat(3)|capitalize }} {% if name|length > 3 %}long{% endif %}"),
+            [(String::from("name"), Variable::new_from_str(" John "))].iter().cloned().collect());
+        match result {
+            Err(e) => { panic!("Failed to render a template: {}", e) },
+            Ok(result) => {
+                assert_eq!(result, "JOHN Ababab long");
+            }
+        }
+    }
+
+    #[test]
+    fn test_engine_render_unknown_filter() {
+        let engine = Engine::new();
+        let result = engine.render(String::from("Hi {% if name|blank %}there{% endif %}"), VariableStore::new());
+        match result {
+            Err(e) => {
+                assert_eq!(e.message, "An error in the Condition Node. Failed to evaluate an expression: Unknown filter \"blank\"");
+                assert_eq!(e.offset, 14);
+            },
+            Ok(_) => panic!("Rendering must have failed."),
+        }
+    }
+
+    #[test]
+    fn test_engine_render_list_and_map_literals() {
+        let engine = Engine::new();
+        let result = engine.render(
+            String::from("{{ {a: {b: 'c'}}.a.b }} {{ [1, 2, 3][1:] }}{% if {x: 1} %} {{ {'k': [1]}|length }}{% endif %}"),
+            VariableStore::new());
+        match result {
+            Err(e) => { panic!("Failed to render a template: {}", e) },
+            Ok(result) => {
+                assert_eq!(result, "c [2, 3] 1");
+            }
+        }
+    }
+
+    #[test]
+    fn test_engine_render_lazy_variables() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let mut variables = VariableStore::new();
+        variables.insert(String::from("count"), Variable::new_lazy(move || {
+            Ok(Variable::new_from_int(counter.fetch_add(1, Ordering::SeqCst) as i128 + 5))
+        }));
+        variables.insert(String::from("report"), Variable::new_lazy(|| panic!("An unused variable must not be computed")));
+        let engine = Engine::new();
+        let result = engine.render(
+            String::from("{{ count }}{% if count > 1 %} items: {{ count }}{% endif %}"),
+            variables.clone());
+        match result {
+            Err(e) => { panic!("Failed to render a template: {}", e) },
+            Ok(result) => {
+                assert_eq!(result, "5 items: 5");
+            }
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Values are computed once per render
+        assert!(engine.render(String::from("{{ count }}"), variables.clone()).is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Lazy variables in lists and maps are computed when printed or passed to a filter
+        variables.insert(String::from("counts"), Variable::from(vec![variables["count"].clone()]));
+        let result = engine.render(String::from("{{ counts }} {{ counts|length }} {{ '' ~ {n: count} }}"), variables);
+        match result {
+            Err(e) => { panic!("Failed to render a template: {}", e) },
+            Ok(result) => {
+                assert_eq!(result, "[7] 1 {\"n\": 7}");
+            }
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_engine_render_lazy_variable_errors() {
+        let mut variables = VariableStore::new();
+        let failing = Variable::new_lazy(|| Err(EvaluationError::new(String::from("No connection"))));
+        variables.insert(String::from("counts"), Variable::from(vec![failing.clone()]));
+        variables.insert(String::from("stats"), Variable::new_from_map([(String::from("unread"), failing)].iter().cloned().collect()));
+        let engine = Engine::new();
+        for template in ["{{ counts }}", "{{ stats }}", "{{ counts|length }}", "{{ 'Stats: ' ~ stats }}"].iter() {
+            match engine.render(String::from(*template), variables.clone()) {
+                Ok(result) => panic!("Rendering {} must have failed, got: {}", template, result),
+                Err(e) => assert!(e.message.ends_with("No connection"), "{}", e.message),
+            }
+        }
+    }
+
+    #[test]
+    fn test_engine_render_undefined_strict() {
+        let engine = Engine::new();
+        let result = engine.render(String::from("Hello,\n{{ user.name }} {{ missing }}"), [
+            (String::from("user"), Variable::new_from_map(VariableStore::new())),
+        ].iter().cloned().collect());
+        match result {
+            Err(e) => {
+                assert_eq!(e.message, "Failed to evaluate an expression: Attribute not found: user.name");
+                assert_eq!(e.offset, 15);
+            },
+            Ok(_) => panic!("Rendering must have failed."),
+        }
+
+        let result = engine.render(String::from("{% if ready %}yes{% elseif items[2] %}no{% endif %}"), [
+            (String::from("ready"), Variable::new_from_boolean(false)),
+            (String::from("items"), Variable::new_from_list(vec![Variable::new_from_int(1)])),
+        ].iter().cloned().collect());
+        match result {
+            Err(e) => {
+                assert_eq!(e.message, "Failed to evaluate an expression: Index 2 is out of range for a list of length 1");
+                assert_eq!(e.offset, 32);
+            },
+            Ok(_) => panic!("Rendering must have failed."),
+        }
+
+        let result = engine.render(
+            String::from("{{ missing ?? 'guest' }} {{ user.name|default('anonymous') }} {{ user.name ?? missing ?? 0 }}"),
+            [(String::from("user"), Variable::new_from_map(VariableStore::new()))].iter().cloned().collect());
+        match result {
+            Err(e) => { panic!("Failed to render a template: {}", e) },
+            Ok(result) => {
+                assert_eq!(result, "guest anonymous 0");
+            }
+        }
+    }
+
+    #[test]
+    fn test_engine_render_undefined_lenient() {
+        let mut engine = Engine::new();
+        engine.set_undefined_policy(UndefinedPolicy::Lenient);
+        let result = engine.render(
+            String::from("[{{ missing }}]{% if not user.name %} anonymous{% endif %}"),
+            [(String::from("user"), Variable::new_from_map(VariableStore::new()))].iter().cloned().collect());
+        match result {
+            Err(e) => { panic!("Failed to render a template: {}", e) },
+            Ok(result) => {
+                assert_eq!(result, "[] anonymous");
+            }
+        }
+
+        // Getting an attribute of an undefined value is still an error
+        let result = engine.render(String::from("{{ missing.name }}"), VariableStore::new());
+        match result {
+            Err(e) => {
+                assert_eq!(e.message, "Failed to evaluate an expression: Cannot get attribute \"name\" of a value of type \"null\": missing");
+                assert_eq!(e.offset, 11);
+            },
+            Ok(_) => panic!("Rendering must have failed."),
+        }
+    }
+
+    #[test]
+    fn test_engine_render_undefined_chainable() {
+        let mut engine = Engine::new();
+        engine.set_undefined_policy(UndefinedPolicy::Chainable);
+        let result = engine.render(
+            String::from("[{{ missing.a.b }}][{{ missing[0].c }}][{{ missing.a ?? 'none' }}]"),
+            VariableStore::new());
+        match result {
+            Err(e) => { panic!("Failed to render a template: {}", e) },
+            Ok(result) => {
+                assert_eq!(result, "[][][none]");
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_engine_render_serialize() {
+        #[derive(serde::Serialize)]
+        struct Item {
+            title: &'static str,
+            price: f64,
+        }
+
+        #[derive(serde::Serialize)]
+        struct Order {
+            customer: Option<&'static str>,
+            items: Vec<Item>,
+        }
+
+        let engine = Engine::new();
+        let order = Order { customer: Some("John"), items: vec![Item { title: "Book", price: 9.5 }] };
+        let result = engine.render_serialize(
+            String::from("{{ customer }}: {{ items[0].title }} for {{ items[0].price }}"),
+            &order);
+        match result {
+            Err(e) => { panic!("Failed to render a template: {}", e) },
+            Ok(result) => {
+                assert_eq!(result, "John: Book for 9.5");
+            }
+        }
+
+        let result = engine.render_serialize(String::from("{{ 1 }}"), &[1, 2]);
+        match result {
+            Err(e) => assert_eq!(e.message, "Failed to serialize the context: Expected a struct or a map, got list"),
+            Ok(_) => panic!("Rendering must have failed."),
+        }
+    }
+}
@@ -2,6 +2,8 @@ use std::fmt;
 use std::error::Error as Error;
 
 pub struct TemplateError {
+    /// Name of the template with the error, `None` for a template rendered from a string
+    pub filename: Option<String>,
     pub message: String,
     pub offset: usize,
     pub template: String,
@@ -10,12 +12,30 @@ pub struct TemplateError {
 impl TemplateError {
     pub fn create(template: String, offset: usize, message: String) -> TemplateError {
         TemplateError{
+            filename: None,
             message: message,
             offset: offset,
             template: template,
         }
     }
 
+    /// Sets the name of the template with the error, unless it is set already
+    /// by an error of a nested template, e.g. of an include inside of an include
+    pub fn with_filename(mut self, filename: &str) -> TemplateError {
+        if self.filename.is_none() {
+            self.filename = Some(String::from(filename));
+        }
+        self
+    }
+
+    fn get_location(&self) -> String {
+        let (line_nr, line_offset) = self.get_line_number_and_offset();
+        match &self.filename {
+            Some(filename) => format!("in {} at line {}, position {}", filename, line_nr, line_offset),
+            None => format!("at line {}, position {}", line_nr, line_offset),
+        }
+    }
+
     fn get_line_number_and_offset(&self) -> (usize, usize) {
         let mut template_remain = self.template.clone();
         let mut abs_offset = 0;
@@ -43,8 +63,7 @@ impl TemplateError {
 
 impl fmt::Debug for TemplateError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let (line_nr, line_offset) = self.get_line_number_and_offset();
-        write!(f,"An error ocurred at line {}, position {}: {}", line_nr, line_offset, self.message.clone())
+        write!(f,"An error ocurred {}: {}", self.get_location(), self.message.clone())
     }
 }
 
@@ -56,8 +75,7 @@ impl Error for TemplateError {
 
 impl fmt::Display for TemplateError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let (line_nr, line_offset) = self.get_line_number_and_offset();
-        write!(f,"An error ocurred at line {}, position {}: {}", line_nr, line_offset, self.message.clone())
+        write!(f,"An error ocurred {}: {}", self.get_location(), self.message.clone())
     }
 }
 
@@ -87,5 +105,9 @@ mod tests {
             String::from("Comment is not closed"));
         let message = format!("{}", error);
         assert_eq!(message, "An error ocurred at line 1, position 13: Comment is not closed");
+
+        let error = error.with_filename("partials/header.twig").with_filename("page.twig");
+        let message = format!("{}", error);
+        assert_eq!(message, "An error ocurred in partials/header.twig at line 1, position 13: Comment is not closed");
     }
 }
\ No newline at end of file
@@ -3,12 +3,27 @@ use std::error::Error as Error;
 
 pub struct EvaluationError {
     pub message: String,
+    /// Position in the expression string, if the error is bound to a part of the expression
+    pub offset: Option<usize>,
+    /// True if an undefined variable, attribute or item is referenced.
+    /// Such errors are caught by the `??` operator and the `default` filter
+    pub is_undefined: bool,
 }
 
 impl EvaluationError {
     pub fn new(message: String) -> EvaluationError {
         EvaluationError {
             message: message,
+            offset: None,
+            is_undefined: false,
+        }
+    }
+
+    pub fn new_undefined(offset: Option<usize>, message: String) -> EvaluationError {
+        EvaluationError {
+            message,
+            offset,
+            is_undefined: true,
         }
     }
 
@@ -0,0 +1,50 @@
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::functions::{self, NamedArgs};
+use crate::common::variable::Variable;
+
+/// Name of the `default` filter. It is applied to undefined values as well, see `FilterCall`
+pub const DEFAULT_FILTER: &str = "default";
+
+/// The argument if the value is undefined, null, false, an empty string, list or map:
+/// `user.nickname|default(user.name)`. Without an argument an empty string is returned
+pub fn default(value: &Variable, args: &[Variable], named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+    functions::check_no_named_args(named_args)?;
+    if args.len() > 1 {
+        return Err(EvaluationError::new(format!("Expected at most 1 argument, got {}", args.len())));
+    }
+    let is_empty = match value {
+        Variable::Null => true,
+        Variable::Boolean(b) => !b,
+        Variable::String(s) => s.is_empty(),
+        Variable::List(l) => l.is_empty(),
+        Variable::Map(m) => m.is_empty(),
+        _ => false,
+    };
+    if !is_empty {
+        return Ok(value.clone());
+    }
+    Ok(match args.first() {
+        Some(default) => default.clone(),
+        None => Variable::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expressions_filters_general_default() {
+        let apply = |value: Variable, args: &[Variable]| default(&value, args, &NamedArgs::new(), &RenderContext::new());
+        let fallback = [Variable::new_from_str("x")];
+        assert_eq!(apply(Variable::new_null(), &fallback).ok().unwrap().get_string_value(), "x");
+        assert_eq!(apply(Variable::new_from_str(""), &fallback).ok().unwrap().get_string_value(), "x");
+        assert_eq!(apply(Variable::new_from_boolean(false), &fallback).ok().unwrap().get_string_value(), "x");
+        assert_eq!(apply(Variable::new_from_list(Vec::new()), &fallback).ok().unwrap().get_string_value(), "x");
+        assert_eq!(apply(Variable::new_from_int(0), &fallback).ok().unwrap().get_int_value(), Some(0));
+        assert_eq!(apply(Variable::new_from_str("a"), &fallback).ok().unwrap().get_string_value(), "a");
+        assert_eq!(apply(Variable::new_null(), &[]).ok().unwrap().get_string_value(), "");
+        assert_eq!(apply(Variable::new_null(), &[Variable::new(), Variable::new()]).err().unwrap().message, "Expected at most 1 argument, got 2");
+    }
+}
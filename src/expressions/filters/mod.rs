@@ -0,0 +1,37 @@
+pub mod general;
+pub mod number;
+pub mod string;
+
+use std::collections::HashMap;
+
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::functions::{self, NamedArgs};
+use crate::common::variable::Variable;
+
+/// A filter applicable in templates: `value|filter(args)`.
+/// Receives the filtered value followed by positional and named arguments
+pub type Filter = fn(value: &Variable, args: &[Variable], named_args: &NamedArgs, context: &RenderContext) -> Result<Variable, EvaluationError>;
+
+pub type FilterStore = HashMap<String, Filter>;
+
+/// Filters available in every engine
+pub fn get_builtin_filters() -> FilterStore {
+    let mut filters = FilterStore::new();
+    filters.insert(String::from("abs"), number::abs as Filter);
+    filters.insert(String::from("capitalize"), string::capitalize as Filter);
+    filters.insert(String::from(general::DEFAULT_FILTER), general::default as Filter);
+    filters.insert(String::from("length"), string::length as Filter);
+    filters.insert(String::from("lower"), string::lower as Filter);
+    filters.insert(String::from("trim"), string::trim as Filter);
+    filters.insert(String::from("upper"), string::upper as Filter);
+    filters
+}
+
+/// Fails if a filter received arguments while it takes none
+pub fn check_no_args(args: &[Variable], named_args: &NamedArgs) -> Result<(), EvaluationError> {
+    if !args.is_empty() {
+        return Err(EvaluationError::new(format!("Expected no arguments, got {}", args.len())));
+    }
+    functions::check_no_named_args(named_args)
+}
@@ -0,0 +1,37 @@
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::filters;
+use crate::expressions::functions::NamedArgs;
+use crate::expressions::nodes::arithmetic;
+use crate::common::variable::Variable;
+
+/// Absolute value: `(-5)|abs = 5`
+pub fn abs(value: &Variable, args: &[Variable], named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+    filters::check_no_args(args, named_args)?;
+    let value = arithmetic::to_number(value.clone());
+    if let Some(v) = value.get_int_value() {
+        match v.checked_abs() {
+            Some(v) => Ok(Variable::new_from_int(v)),
+            None => Err(EvaluationError::new(format!("Integer overflow: abs({})", v))),
+        }
+    } else if let Some(v) = value.get_float_value() {
+        Ok(Variable::new_from_float(v.abs()))
+    } else {
+        Err(EvaluationError::new(format!("Expected a number, got {}: {}", value.get_type_name(), value.get_string_value())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expressions_filters_number_abs() {
+        let apply = |value: Variable| abs(&value, &[], &NamedArgs::new(), &RenderContext::new());
+        assert_eq!(apply(Variable::new_from_int(-5)).ok().unwrap().get_int_value(), Some(5));
+        assert_eq!(apply(Variable::new_from_float(-2.5)).ok().unwrap().get_float_value(), Some(2.5));
+        assert_eq!(apply(Variable::new_from_str("-3")).ok().unwrap().get_int_value(), Some(3));
+        assert_eq!(apply(Variable::new_from_str("abc")).err().unwrap().message, "Expected a number, got string: abc");
+        assert_eq!(apply(Variable::new_from_int(i128::MIN)).err().unwrap().message, format!("Integer overflow: abs({})", i128::MIN));
+    }
+}
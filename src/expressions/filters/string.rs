@@ -0,0 +1,94 @@
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::filters;
+use crate::expressions::functions::{self, NamedArgs};
+use crate::common::variable::Variable;
+
+/// `"Hello"|upper = "HELLO"`
+pub fn upper(value: &Variable, args: &[Variable], named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+    filters::check_no_args(args, named_args)?;
+    Ok(Variable::new_from_string(value.get_string_value().to_uppercase()))
+}
+
+/// `"Hello"|lower = "hello"`
+pub fn lower(value: &Variable, args: &[Variable], named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+    filters::check_no_args(args, named_args)?;
+    Ok(Variable::new_from_string(value.get_string_value().to_lowercase()))
+}
+
+/// `"hello WORLD"|capitalize = "Hello world"`
+pub fn capitalize(value: &Variable, args: &[Variable], named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+    filters::check_no_args(args, named_args)?;
+    let string = value.get_string_value();
+    let mut chars = string.chars();
+    let result = match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str().to_lowercase().as_str(),
+        None => String::new(),
+    };
+    Ok(Variable::new_from_string(result))
+}
+
+/// Removes whitespace from both sides: `"  hi "|trim = "hi"`.
+/// Other characters are trimmed if passed: `"--hi-"|trim("-") = "hi"`
+pub fn trim(value: &Variable, args: &[Variable], named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+    functions::check_no_named_args(named_args)?;
+    let string = value.get_string_value();
+    let result = match args {
+        [] => string.trim(),
+        [characters] => {
+            let characters = characters.get_string_value();
+            string.trim_matches(|c| characters.contains(c))
+        },
+        _ => return Err(EvaluationError::new(format!("Expected at most one argument, got {}", args.len()))),
+    };
+    Ok(Variable::new_from_str(result))
+}
+
+/// Number of characters in a string or items in a list or map: `"héllo"|length = 5`
+pub fn length(value: &Variable, args: &[Variable], named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+    filters::check_no_args(args, named_args)?;
+    let length = if let Some(list) = value.get_list_value() {
+        list.len()
+    } else if let Some(store) = value.get_struct_value() {
+        store.len()
+    } else {
+        value.get_string_value().chars().count()
+    };
+    Ok(Variable::new_from_int(length as i128))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(filter: filters::Filter, value: &str, args: &[Variable]) -> String {
+        match filter(&Variable::new_from_str(value), args, &NamedArgs::new(), &RenderContext::new()) {
+            Ok(v) => v.get_string_value(),
+            Err(e) => panic!("Expected a value, got an error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_expressions_filters_string() {
+        assert_eq!(apply(upper, "Hello", &[]), "HELLO");
+        assert_eq!(apply(lower, "Hello", &[]), "hello");
+        assert_eq!(apply(capitalize, "hello WORLD", &[]), "Hello world");
+        assert_eq!(apply(capitalize, "", &[]), "");
+        assert_eq!(apply(trim, " \thi \n", &[]), "hi");
+        assert_eq!(apply(trim, "--hi-", &[Variable::new_from_str("-")]), "hi");
+        assert_eq!(apply(length, "héllo", &[]), "5");
+    }
+
+    #[test]
+    fn test_expressions_filters_string_unexpected_args() {
+        let err = upper(&Variable::new_from_str("a"), &[Variable::new_from_int(1)], &NamedArgs::new(), &RenderContext::new());
+        assert_eq!(err.err().unwrap().message, "Expected no arguments, got 1");
+    }
+
+    #[test]
+    fn test_expressions_filters_string_length_list() {
+        let value = Variable::new_from_list(vec![Variable::new_from_int(1), Variable::new_from_int(2)]);
+        let result = length(&value, &[], &NamedArgs::new(), &RenderContext::new());
+        assert_eq!(result.ok().unwrap().get_int_value(), Some(2));
+    }
+}
@@ -0,0 +1,33 @@
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::functions::NamedArgs;
+use crate::common::variable::{Variable, VariableStore};
+use crate::nodes::tags::include::{self, IncludeError};
+
+/// `include(name, variables, with_context=true, ignore_missing=false)` — renders a template, like `{% include %}`
+pub fn include(args: &[Variable], named_args: &NamedArgs, context: &RenderContext) -> Result<Variable, EvaluationError> {
+    let (name, variables) = match args {
+        [] | [_, _, _, ..] => return Err(EvaluationError::new(format!("Expected 1 or 2 arguments, got {}", args.len()))),
+        [Variable::String(name)] => (name, VariableStore::new()),
+        [Variable::String(name), Variable::Map(variables)] => (name, variables.clone()),
+        [Variable::String(_), other] => return Err(EvaluationError::new(format!("Variables must be a map, got {}", other.get_type_name()))),
+        [other, ..] => return Err(EvaluationError::new(format!("A template name must be a string, got {}", other.get_type_name()))),
+    };
+    let mut with_context = true;
+    let mut ignore_missing = false;
+    for (arg_name, value) in named_args {
+        match arg_name.as_str() {
+            "with_context" => with_context = value.get_boolean_value(),
+            "ignore_missing" => ignore_missing = value.get_boolean_value(),
+            _ => return Err(EvaluationError::new(format!("Unexpected named argument \"{}\"", arg_name))),
+        }
+    }
+
+    let mut parameters = if with_context { context.get_template_variables() } else { VariableStore::new() };
+    parameters.extend(variables);
+    match include::render_include(context, name, parameters, ignore_missing) {
+        Ok(result) => Ok(Variable::from(result)),
+        Err(IncludeError::Load(message)) => Err(EvaluationError::new(message)),
+        Err(IncludeError::Template(e)) => Err(EvaluationError::new(e.to_string())),
+    }
+}
@@ -0,0 +1,26 @@
+use std::cmp::Ordering;
+
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::functions::{self, NamedArgs};
+use crate::common::variable::Variable;
+
+/// `max(a, b, ...)` — the biggest of the arguments
+pub fn max(args: &[Variable], named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+    functions::find_extremum(args, named_args, Ordering::Greater)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expressions_functions_max() {
+        let args = [Variable::new_from_int(3), Variable::new_from_float(7.5), Variable::new_from_int(-1)];
+        let result = match max(&args, &NamedArgs::new(), &RenderContext::new()) {
+            Ok(v) => v,
+            Err(e) => panic!("Expected a value, got an error: {}", e),
+        };
+        assert_eq!(result.get_float_value(), Some(7.5));
+    }
+}
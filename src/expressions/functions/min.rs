@@ -0,0 +1,35 @@
+use std::cmp::Ordering;
+
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::functions::{self, NamedArgs};
+use crate::common::variable::Variable;
+
+/// `min(a, b, ...)` — the smallest of the arguments
+pub fn min(args: &[Variable], named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+    functions::find_extremum(args, named_args, Ordering::Less)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expressions_functions_min() {
+        let args = [Variable::new_from_str("beta"), Variable::new_from_str("alpha")];
+        let result = match min(&args, &NamedArgs::new(), &RenderContext::new()) {
+            Ok(v) => v,
+            Err(e) => panic!("Expected a value, got an error: {}", e),
+        };
+        assert_eq!(result.get_string_value(), "alpha");
+    }
+
+    #[test]
+    fn test_expressions_functions_min_errors() {
+        let err = min(&[], &NamedArgs::new(), &RenderContext::new()).err().unwrap();
+        assert_eq!(err.message, "Expected at least one argument");
+        let args = [Variable::new_from_int(1), Variable::new_from_str("a")];
+        let err = min(&args, &NamedArgs::new(), &RenderContext::new()).err().unwrap();
+        assert_eq!(err.message, "Cannot compare values of types \"int\" and \"string\": 1 and a");
+    }
+}
@@ -1 +1,53 @@
-pub type Function = fn();
\ No newline at end of file
+pub mod include;
+pub mod max;
+pub mod min;
+pub mod range;
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::nodes::comparison;
+use crate::common::variable::Variable;
+
+/// Named arguments of a function call, e.g. `format(value, precision=2)`
+pub type NamedArgs = HashMap<String, Variable>;
+
+/// A function callable from templates. Receives positional and named arguments
+pub type Function = fn(args: &[Variable], named_args: &NamedArgs, context: &RenderContext) -> Result<Variable, EvaluationError>;
+
+pub type FunctionStore = HashMap<String, Function>;
+
+/// Functions available in every engine
+pub fn get_builtin_functions() -> FunctionStore {
+    let mut functions = FunctionStore::new();
+    functions.insert(String::from("include"), include::include as Function);
+    functions.insert(String::from("max"), max::max as Function);
+    functions.insert(String::from("min"), min::min as Function);
+    functions.insert(String::from("range"), range::range as Function);
+    functions
+}
+
+/// Fails if a function which takes no named arguments received some
+pub fn check_no_named_args(named_args: &NamedArgs) -> Result<(), EvaluationError> {
+    match named_args.keys().next() {
+        Some(name) => Err(EvaluationError::new(format!("Unexpected named argument \"{}\"", name))),
+        None => Ok(()),
+    }
+}
+
+// Returns the argument which compares as `ordering` to all others
+fn find_extremum(args: &[Variable], named_args: &NamedArgs, ordering: Ordering) -> Result<Variable, EvaluationError> {
+    check_no_named_args(named_args)?;
+    let mut result = match args.first() {
+        Some(a) => a,
+        None => return Err(EvaluationError::new(String::from("Expected at least one argument"))),
+    };
+    for arg in args[1..].iter() {
+        if comparison::compare(result, arg)? == ordering.reverse() {
+            result = arg;
+        }
+    }
+    Ok(result.clone())
+}
@@ -0,0 +1,63 @@
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::functions::{self, NamedArgs};
+use crate::common::variable::Variable;
+
+/// Upper limit of a range length, protects from accidental huge allocations
+const MAX_RANGE_LENGTH: i128 = 1_000_000;
+
+/// `range(start, end, step)` — a list of integers from start to end inclusive.
+/// Step is 1 by default and may be negative: `range(5, 1, -2) = [5, 3, 1]`
+pub fn range(args: &[Variable], named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+    functions::check_no_named_args(named_args)?;
+    let (start, end, step) = match args {
+        [start, end] => (get_int(start)?, get_int(end)?, 1),
+        [start, end, step] => (get_int(start)?, get_int(end)?, get_int(step)?),
+        _ => return Err(EvaluationError::new(format!("Expected 2 or 3 arguments, got {}", args.len()))),
+    };
+    if step == 0 {
+        return Err(EvaluationError::new(String::from("Step must not be zero")));
+    }
+    let length = if (end - start).signum() == -step.signum() { 0 } else { (end - start) / step + 1 };
+    if length > MAX_RANGE_LENGTH {
+        return Err(EvaluationError::new(format!("Range is too long: {} items, the maximum is {}", length, MAX_RANGE_LENGTH)));
+    }
+    let list = (0..length).map(|i| Variable::new_from_int(start + i * step)).collect();
+    Ok(Variable::new_from_list(list))
+}
+
+fn get_int(value: &Variable) -> Result<i128, EvaluationError> {
+    match value.get_int_value() {
+        Some(i) => Ok(i),
+        None => Err(EvaluationError::new(format!("Expected an integer, got {}: {}", value.get_type_name(), value.get_string_value()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(args: &[i128]) -> Result<String, String> {
+        let args: Vec<Variable> = args.iter().map(|a| Variable::new_from_int(*a)).collect();
+        match range(&args, &NamedArgs::new(), &RenderContext::new()) {
+            Ok(v) => Ok(v.get_string_value()),
+            Err(e) => Err(e.message),
+        }
+    }
+
+    #[test]
+    fn test_expressions_functions_range() {
+        assert_eq!(call(&[1, 5]), Ok(String::from("[1, 2, 3, 4, 5]")));
+        assert_eq!(call(&[0, 10, 4]), Ok(String::from("[0, 4, 8]")));
+        assert_eq!(call(&[5, 1, -2]), Ok(String::from("[5, 3, 1]")));
+        assert_eq!(call(&[3, 3]), Ok(String::from("[3]")));
+        assert_eq!(call(&[5, 1]), Ok(String::from("[]")));
+    }
+
+    #[test]
+    fn test_expressions_functions_range_errors() {
+        assert_eq!(call(&[1]), Err(String::from("Expected 2 or 3 arguments, got 1")));
+        assert_eq!(call(&[1, 5, 0]), Err(String::from("Step must not be zero")));
+        assert_eq!(call(&[0, 10_000_000]), Err(String::from("Range is too long: 10000001 items, the maximum is 1000000")));
+    }
+}
@@ -1,19 +1,32 @@
+use std::iter::Peekable;
+use std::vec::IntoIter;
+
+use crate::context::parsing_context::ParsingContext;
 use crate::expressions::errors::parsing_error::ParsingError;
-use crate::expressions::nodes::{Node, NodeCreateResult};
+use crate::expressions::nodes::{Associativity, Node, NodeCreateResult, NodeCreator};
 use crate::expressions::nodes::NODE_CREATORS;
+use crate::expressions::nodes::{postfix, unary};
 
 pub mod errors;
+pub mod filters;
 pub mod functions;
 pub mod nodes;
 
-fn get_parsed_node(string_remain: String, offset: usize) -> Result<(Box<dyn Node>, usize), ParsingError> {
-    for node_creator in &NODE_CREATORS {
-        match node_creator(string_remain.clone(), offset) {
+fn get_parsed_node(string_remain: String, offset: usize, is_operand_expected: bool, context: &ParsingContext) -> Result<(Box<dyn Node>, usize), ParsingError> {
+    // "-" and "!" are unary operators where an operand is expected, and binary ones otherwise.
+    // Filters, attributes and subscripts follow an operand
+    let positional_node_creators: &[NodeCreator] = if is_operand_expected {
+        &[unary::try_create_from_string]
+    } else {
+        &[postfix::try_create_from_string]
+    };
+    for node_creator in positional_node_creators.iter().chain(NODE_CREATORS.iter()) {
+        match node_creator(string_remain.clone(), offset, context) {
             NodeCreateResult::Some(r) => {
                 return Ok(r);
             },
             NodeCreateResult::Err(e) => {
-                return Err(ParsingError::new(offset, format!("An error occurred on parsing the expresion: {}", e)));
+                return Err(e);
             },
             NodeCreateResult::None => {}, // proceed with iteration over node creators
         }
@@ -21,75 +34,301 @@ fn get_parsed_node(string_remain: String, offset: usize) -> Result<(Box<dyn Node
     Err(ParsingError::new(offset, format!("Cannot parse the part of expression: \"{}\"", string_remain)))
 }
 
-pub fn parse(string: String) -> Result<Box<dyn Node>, ParsingError> {
-    let mut string_remain = string.clone();
-    let mut offset: usize = 0;
+/// A node parsed from the expression string together with its offset
+struct Token {
+    node: Box<dyn Node>,
+    offset: usize,
+}
+
+type TokenIterator = Peekable<IntoIter<Token>>;
+
+// Splits an expression string into a flat list of nodes (operands and operators)
+fn tokenize(string: &str, base_offset: usize, context: &ParsingContext) -> Result<Vec<Token>, ParsingError> {
+    let mut string_remain = String::from(string);
+    let mut offset: usize = base_offset;
     let mut prev_string_remain_len = string_remain.len() + 1;
-    let mut nodes_stack: Vec<Box<dyn Node>> = Vec::new();
-    while string_remain.len() > 0 {
+    let mut tokens: Vec<Token> = Vec::new();
+    while !string_remain.is_empty() {
         if string_remain.len() >= prev_string_remain_len {
             return Err(ParsingError::new(offset, String::from("An infinite loop detected")));
         }
         prev_string_remain_len = string_remain.len();
 
         let string_remain_orig_len = string_remain.len();
-        string_remain = string_remain.trim_start_matches(" ").to_string();
+        string_remain = string_remain.trim_start_matches(' ').to_string();
         offset += string_remain_orig_len - string_remain.len();
-        if string_remain.len() == 0 {
+        if string_remain.is_empty() {
             break;
         }
+        if string_remain.starts_with(')') {
+            return Err(ParsingError::new(offset, String::from("Unexpected closing bracket")));
+        }
+
+        let is_operand_expected = match tokens.last() {
+            Some(token) => token.node.is_operator() || token.node.is_unary_operator(),
+            None => true,
+        };
+        let (node, offset_increment) = get_parsed_node(string_remain.clone(), offset, is_operand_expected, context)?;
+        tokens.push(Token { node, offset });
+        offset += offset_increment;
+        string_remain = if string_remain.len() > offset_increment {
+            string_remain[offset_increment..].to_string()
+        } else {
+            String::new()
+        };
+    }
+    Ok(tokens)
+}
+
+fn parse_operand(tokens: &mut TokenIterator, end_offset: usize) -> Result<Box<dyn Node>, ParsingError> {
+    match tokens.next() {
+        None => Err(ParsingError::new(end_offset, String::from("Unexpected end of expression. Expected an operand"))),
+        Some(token) => {
+            if token.node.is_operator() {
+                return Err(ParsingError::new(token.offset,
+                    format!("Expected an operand, got operator \"{}\"", token.node.get_type())));
+            }
+            let mut node = token.node;
+            if node.is_unary_operator() {
+                let precedence = node.get_precedence();
+                node.set_unary_operand(Some(parse_binary_expression(tokens, precedence, end_offset)?));
+                return Ok(node);
+            }
+            // Postfix operators bind stronger than any other: "-a|abs" is "-(a|abs)"
+            while tokens.peek().is_some_and(|t| t.node.is_postfix_operator()) {
+                let mut postfix_operator = tokens.next().unwrap().node;
+                postfix_operator.set_unary_operand(Some(node));
+                node = postfix_operator;
+            }
+            Ok(node)
+        },
+    }
+}
 
-        let node = match get_parsed_node(string_remain.clone(), offset) {
-            Ok(r) => {
-                let (parsed_node, offset_increment) = r;
-                offset += offset_increment;
-                string_remain = if string_remain.len() > offset_increment {
-                    string_remain[offset_increment..].to_string()
-                } else {
-                    String::new()
+// Precedence climbing: consumes operators which bind at least as strong as min_precedence
+fn parse_binary_expression(tokens: &mut TokenIterator, min_precedence: usize, end_offset: usize) -> Result<Box<dyn Node>, ParsingError> {
+    let mut left_operand = parse_operand(tokens, end_offset)?;
+    loop {
+        let precedence = match tokens.peek() {
+            None => break,
+            Some(token) => {
+                if !token.node.is_operator() {
+                    return Err(ParsingError::new(token.offset,
+                        format!("Expected an operator, got \"{}\"", token.node.get_type())));
+                }
+                token.node.get_precedence()
+            },
+        };
+        if precedence < min_precedence {
+            break;
+        }
+
+        let mut operator = tokens.next().unwrap().node;
+        let next_min_precedence = match operator.get_associativity() {
+            Associativity::Left => precedence + 1,
+            Associativity::Right => precedence,
+        };
+        let right_operand = parse_binary_expression(tokens, next_min_precedence, end_offset)?;
+        operator.set_binary_operands([Some(left_operand), Some(right_operand)]);
+        left_operand = operator;
+    }
+    Ok(left_operand)
+}
+
+pub fn parse(string: String, context: &ParsingContext) -> Result<Box<dyn Node>, ParsingError> {
+    parse_sub_expression(&string, 0, context)
+}
+
+/// Parses a part of a bigger expression (e.g. contents of brackets).
+/// Offsets of parsing errors are shifted by base_offset
+pub fn parse_sub_expression(string: &str, base_offset: usize, context: &ParsingContext) -> Result<Box<dyn Node>, ParsingError> {
+    let tokens = tokenize(string, base_offset, context)?;
+    if tokens.is_empty() {
+        return Err(ParsingError::new(base_offset, format!("Failed to parse an expression: \"{}\"", string)));
+    }
+    let mut tokens = tokens.into_iter().peekable();
+    parse_binary_expression(&mut tokens, 0, base_offset + string.len())
+}
+
+/// Finds a bracket which closes the one at the beginning of the string.
+/// Nested brackets of all kinds and quoted strings are skipped.
+/// Returns a position of the closing bracket
+///
+/// # Examples
+///
+/// ```
+/// use pete_core::expressions::find_closing_bracket;
+///
+/// assert_eq!(find_closing_bracket("(2 + (3 * 4)) * 5", 0).ok(), Some(12));
+/// assert_eq!(find_closing_bracket("(\")\") + 1", 0).ok(), Some(4));
+/// assert_eq!(find_closing_bracket("(2 + 3", 10).err().unwrap().offset, 10);
+/// ```
+pub fn find_closing_bracket(string: &str, offset: usize) -> Result<usize, ParsingError> {
+    let bytes = string.as_bytes();
+    let mut open_brackets: Vec<(u8, usize)> = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'"' | b'\'' => match find_closing_quote(&string[pos..]) {
+                Some(quote_pos) => pos += quote_pos,
+                None => return Err(ParsingError::new(offset + pos, String::from("String is not closed"))),
+            },
+            b'(' | b'[' | b'{' => open_brackets.push((bytes[pos], pos)),
+            c @ (b')' | b']' | b'}') => {
+                let expected_opening = match c {
+                    b')' => b'(',
+                    b']' => b'[',
+                    _ => b'{',
+                };
+                match open_brackets.pop() {
+                    Some((opening, _)) if opening == expected_opening => {},
+                    _ => return Err(ParsingError::new(offset + pos, format!("Unexpected closing bracket \"{}\"", c as char))),
                 };
-                Ok(parsed_node)
+                if open_brackets.is_empty() {
+                    return Ok(pos);
+                }
             },
-            Err(e) => Err(e),
-        }?;
+            _ => {},
+        }
+        if open_brackets.is_empty() {
+            break;
+        }
+        pos += 1;
+    }
+    let unclosed_pos = match open_brackets.last() {
+        Some((_, pos)) => *pos,
+        None => 0,
+    };
+    Err(ParsingError::new(offset + unclosed_pos, String::from("Bracket is not closed")))
+}
 
-        let last_node = if !node.is_operator() && nodes_stack.len() >= 2 {
-            let mut operator = nodes_stack.pop().unwrap();
-            if !operator.is_operator() {
-                return Err(ParsingError::new(offset, format!("Expected the previous node to be operator")));
-            }
-            let first_operand = nodes_stack.pop();
-            operator.set_binary_operands([first_operand, Some(node)]);
-            operator
-        } else {
-            node
+/// Finds a quote which closes the string literal at the beginning of the string.
+/// Escaped quotes and quotes inside of interpolations (`#{...}`) are skipped
+///
+/// # Examples
+///
+/// ```
+/// use pete_core::expressions::find_closing_quote;
+///
+/// assert_eq!(find_closing_quote("'it\\'s' ~ name"), Some(6));
+/// assert_eq!(find_closing_quote("\"#{ \"}\" }\" }}"), Some(9));
+/// assert_eq!(find_closing_quote("\"not closed"), None);
+/// ```
+pub fn find_closing_quote(string: &str) -> Option<usize> {
+    let bytes = string.as_bytes();
+    let quote = *bytes.first()?;
+    let mut pos = 1;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'\\' => pos += 1, // the next character is escaped
+            b'#' if quote == b'"' && bytes.get(pos + 1) == Some(&b'{') => {
+                pos += 1 + find_closing_bracket(&string[pos+1..], 0).ok()?;
+            },
+            c if c == quote => return Some(pos),
+            _ => {},
+        }
+        pos += 1;
+    }
+    None
+}
+
+/// Splits a comma-separated list (e.g. arguments of a function call) into items.
+/// Commas inside of brackets and quoted strings are skipped, a trailing comma is allowed.
+/// Returns items together with their positions
+///
+/// # Examples
+///
+/// ```
+/// use pete_core::expressions::split_list_items;
+///
+/// let items = split_list_items("1, (2, 3), 'a,b',", 0).unwrap();
+/// assert_eq!(items, vec![(0, "1"), (2, " (2, 3)"), (10, " 'a,b'")]);
+/// assert_eq!(split_list_items("1,,2", 10).err().unwrap().offset, 12);
+/// ```
+pub fn split_list_items(string: &str, offset: usize) -> Result<Vec<(usize, &str)>, ParsingError> {
+    let mut items: Vec<(usize, &str)> = Vec::new();
+    let mut item_start = 0;
+    loop {
+        let item_end = match find_top_level_char(&string[item_start..], b',', offset + item_start)? {
+            Some(p) => item_start + p,
+            None => string.len(),
         };
-        nodes_stack.push(last_node);
+        let item = &string[item_start..item_end];
+        if item.trim().is_empty() {
+            if item_end < string.len() {
+                return Err(ParsingError::new(offset + item_end, String::from("Unexpected comma")));
+            }
+            break; // an empty list or a trailing comma
+        }
+        items.push((item_start, item));
+        if item_end == string.len() {
+            break;
+        }
+        item_start = item_end + 1;
     }
-    match nodes_stack.pop() {
-        Some(r) => Ok(r),
-        None => Err(ParsingError::new(0, format!("Failed to parse an expression: \"{}\"", string)))
+    Ok(items)
+}
+
+/// Finds the first occurrence of a character outside of brackets and quoted strings
+///
+/// # Examples
+///
+/// ```
+/// use pete_core::expressions::find_top_level_char;
+///
+/// assert_eq!(find_top_level_char("a[1:2]:'b:c'", b':', 0).ok(), Some(Some(6)));
+/// assert_eq!(find_top_level_char("(a:b)", b':', 0).ok(), Some(None));
+/// ```
+pub fn find_top_level_char(string: &str, character: u8, offset: usize) -> Result<Option<usize>, ParsingError> {
+    let bytes = string.as_bytes();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            c if c == character => return Ok(Some(pos)),
+            b'"' | b'\'' => match find_closing_quote(&string[pos..]) {
+                Some(quote_pos) => pos += quote_pos,
+                None => return Err(ParsingError::new(offset + pos, String::from("String is not closed"))),
+            },
+            b'(' | b'[' | b'{' => pos += find_closing_bracket(&string[pos..], offset + pos)?,
+            _ => {},
+        }
+        pos += 1;
     }
+    Ok(None)
 }
 
-// Calculates end offset of expression
+// Calculates end offset of expression. Endings inside of string literals and brackets are skipped,
+// so the string must start after the opening tag
 // get_end_offset("2+3 }} hello", "}}") = 5
 // get_end_offset("22-1 -%} hello", "%}") = 7
-pub fn get_end_offset(string: &String, ending: &str) -> Option<usize> {
-    match string.find(ending) {
-        Some(offset) => Some(offset - 1 + ending.len()), // TODO: consider quoted strings
-        None => None
+// get_end_offset(" {a: {b: 1}} }}", "}}") = 14
+pub fn get_end_offset(string: &str, ending: &str) -> Option<usize> {
+    let bytes = string.as_bytes();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        if bytes[pos..].starts_with(ending.as_bytes()) {
+            return Some(pos + ending.len() - 1);
+        }
+        match bytes[pos] {
+            b'"' | b'\'' => pos += find_closing_quote(&string[pos..])?,
+            // an unclosed bracket is reported by the expression parser
+            b'(' | b'[' | b'{' => pos += find_closing_bracket(&string[pos..], 0).unwrap_or(0),
+            _ => {},
+        }
+        pos += 1;
     }
+    None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::context::render_context::RenderContext;
+    use crate::common::variable::Variable;
 
     #[test]
     fn test_expressions_parse_string_literal_only() {
-        let literal = match parse(String::from(" \"hello, world!\"  ")) {
+        let literal = match parse(String::from(" \"hello, world!\"  "), &ParsingContext::new()) {
             Ok(l) => l,
             Err(e) => panic!("Expected a literal, got an error: {}", e)
         };
@@ -102,7 +341,7 @@ mod tests {
 
     #[test]
     fn test_expressions_parse_int_literal_only() {
-        let literal = match parse(String::from(" 123  ")) {
+        let literal = match parse(String::from(" 123  "), &ParsingContext::new()) {
             Ok(l) => l,
             Err(e) => panic!("Expected a literal, got an error: {}", e)
         };
@@ -115,7 +354,7 @@ mod tests {
 
     #[test]
     fn test_expressions_parse_sum_of_int_space() {
-        let literal = match parse(String::from("3 + 2 + 8")) {
+        let literal = match parse(String::from("3 + 2 + 8"), &ParsingContext::new()) {
             Ok(l) => l,
             Err(e) => panic!("Expected a literal, got an error: {}", e)
         };
@@ -128,7 +367,7 @@ mod tests {
 
     #[test]
     fn test_expressions_parse_sum_of_int_nospace() {
-        let literal = match parse(String::from("4+2")) {
+        let literal = match parse(String::from("4+2"), &ParsingContext::new()) {
             Ok(l) => l,
             Err(e) => panic!("Expected a literal, got an error: {}", e)
         };
@@ -141,7 +380,7 @@ mod tests {
 
     #[test]
     fn test_expressions_parse_logical_and() {
-        let literal = match parse(String::from("0 and 0")) {
+        let literal = match parse(String::from("0 and 0"), &ParsingContext::new()) {
             Ok(l) => l,
             Err(e) => panic!("Expected a literal, got an error: {}", e)
         };
@@ -152,6 +391,189 @@ mod tests {
         assert_eq!(param.get_boolean_value(), false);
     }
 
+    fn get_parsing_context() -> ParsingContext {
+        let mut context = ParsingContext::new();
+        context.filters = filters::get_builtin_filters();
+        context.functions = functions::get_builtin_functions();
+        context
+    }
+
+    fn parse_and_evaluate(expression: &str) -> Variable {
+        let node = match parse(String::from(expression), &get_parsing_context()) {
+            Ok(n) => n,
+            Err(e) => panic!("Expected a node, got an error: {}", e)
+        };
+        match node.evaluate(&RenderContext::new()) {
+            Ok(p) => p,
+            Err(e) => panic!("Expected a parameter, got an error: {}", e)
+        }
+    }
+
+    #[test]
+    fn test_expressions_parse_precedence_arithmetic() {
+        assert_eq!(parse_and_evaluate("2 + 3 * 4").get_int_value(), Some(14));
+        assert_eq!(parse_and_evaluate("2 * 3 + 4").get_int_value(), Some(10));
+        assert_eq!(parse_and_evaluate("1 + 10 % 4 * 2").get_int_value(), Some(5));
+        assert_eq!(parse_and_evaluate("2 + 9 / 3").get_float_value(), Some(5.0));
+    }
+
+    #[test]
+    fn test_expressions_parse_left_associativity() {
+        assert_eq!(parse_and_evaluate("10 - 4 - 3").get_int_value(), Some(3));
+        assert_eq!(parse_and_evaluate("24 / 4 / 2").get_float_value(), Some(3.0));
+    }
+
+    #[test]
+    fn test_expressions_parse_precedence_logic() {
+        assert!(parse_and_evaluate("1 or 0 and 0").get_boolean_value());
+        assert!(!parse_and_evaluate("0 and 1 or 0").get_boolean_value());
+        assert!(parse_and_evaluate("0 and 1 or 2 - 1").get_boolean_value());
+    }
+
+    #[test]
+    fn test_expressions_parse_missing_operand() {
+        match parse(String::from("2 + 3 *"), &ParsingContext::new()) {
+            Ok(_) => panic!("Expected an error, got a node"),
+            Err(e) => {
+                assert_eq!(e.offset, 7);
+                assert_eq!(e.message, "Unexpected end of expression. Expected an operand");
+            },
+        };
+        match parse(String::from("2 + * 3"), &ParsingContext::new()) {
+            Ok(_) => panic!("Expected an error, got a node"),
+            Err(e) => {
+                assert_eq!(e.offset, 4);
+                assert_eq!(e.message, "Expected an operand, got operator \"multiplication\"");
+            },
+        };
+    }
+
+    #[test]
+    fn test_expressions_parse_missing_operator() {
+        match parse(String::from("2 3"), &ParsingContext::new()) {
+            Ok(_) => panic!("Expected an error, got a node"),
+            Err(e) => assert_eq!(e.offset, 2),
+        };
+    }
+
+    #[test]
+    fn test_expressions_parse_parentheses() {
+        assert_eq!(parse_and_evaluate("(2 + 3) * 4").get_int_value(), Some(20));
+        assert_eq!(parse_and_evaluate("2 * (3 + (10 - 2 * (1 + 1)))").get_int_value(), Some(18));
+        assert_eq!(parse_and_evaluate("((7))").get_int_value(), Some(7));
+        assert!(!parse_and_evaluate("(1 or 0) and 0").get_boolean_value());
+    }
+
+    #[test]
+    fn test_expressions_parse_unbalanced_brackets() {
+        match parse(String::from("(2 + (3 * 4) + 1"), &ParsingContext::new()) {
+            Ok(_) => panic!("Expected an error, got a node"),
+            Err(e) => {
+                assert_eq!(e.offset, 0);
+                assert_eq!(e.message, "Bracket is not closed");
+            },
+        };
+        match parse(String::from("2 * (3 + 4))"), &ParsingContext::new()) {
+            Ok(_) => panic!("Expected an error, got a node"),
+            Err(e) => {
+                assert_eq!(e.offset, 11);
+                assert_eq!(e.message, "Unexpected closing bracket");
+            },
+        };
+        match parse(String::from("1 + (2 * (3 + 4)"), &ParsingContext::new()) {
+            Ok(_) => panic!("Expected an error, got a node"),
+            Err(e) => assert_eq!(e.offset, 4),
+        };
+        match parse(String::from("(1 + (2 * 3 + ) 4)"), &ParsingContext::new()) {
+            Ok(_) => panic!("Expected an error, got a node"),
+            Err(e) => assert_eq!(e.offset, 14),
+        };
+    }
+
+    #[test]
+    fn test_expressions_parse_unary_negation() {
+        assert_eq!(parse_and_evaluate("-5").get_int_value(), Some(-5));
+        assert_eq!(parse_and_evaluate("-5 + 3").get_int_value(), Some(-2));
+        assert_eq!(parse_and_evaluate("2 - -3").get_int_value(), Some(5));
+        assert_eq!(parse_and_evaluate("2 * -3").get_int_value(), Some(-6));
+        assert_eq!(parse_and_evaluate("- - 4").get_int_value(), Some(4));
+        assert_eq!(parse_and_evaluate("-(2 + 3) * 2").get_int_value(), Some(-10));
+        assert_eq!(parse_and_evaluate("-170141183460469231731687303715884105728").get_int_value(), Some(i128::MIN));
+        assert_eq!(parse_and_evaluate("-170141183460469231731687303715884105727").get_int_value(), Some(-i128::MAX));
+        assert_eq!(parse_and_evaluate("1 - 170141183460469231731687303715884105727").get_int_value(), Some(1 - i128::MAX));
+    }
+
+    #[test]
+    fn test_expressions_parse_unary_not() {
+        assert!(!parse_and_evaluate("not 1").get_boolean_value());
+        assert!(parse_and_evaluate("!0").get_boolean_value());
+        assert!(!parse_and_evaluate("not 0 and 0").get_boolean_value());
+        assert!(parse_and_evaluate("not (0 and 0)").get_boolean_value());
+        assert!(parse_and_evaluate("!0 or 0").get_boolean_value());
+        assert!(!parse_and_evaluate("not not 0").get_boolean_value());
+        assert!(parse_and_evaluate("1 and not 0").get_boolean_value());
+        assert!(parse(String::from("not_found or not not_found"), &ParsingContext::new()).is_ok());
+    }
+
+    #[test]
+    fn test_expressions_parse_unary_missing_operand() {
+        match parse(String::from("2 + -"), &ParsingContext::new()) {
+            Ok(_) => panic!("Expected an error, got a node"),
+            Err(e) => {
+                assert_eq!(e.offset, 5);
+                assert_eq!(e.message, "Unexpected end of expression. Expected an operand");
+            },
+        };
+    }
+
+    #[test]
+    fn test_expressions_parse_comparison() {
+        assert!(parse_and_evaluate("2 + 2 == 4").get_boolean_value());
+        assert!(parse_and_evaluate("2 * 3 != 5").get_boolean_value());
+        assert!(parse_and_evaluate("1 < 2 and 3 >= 3").get_boolean_value());
+        assert!(parse_and_evaluate("1 + 1 > 1 and 2 < 1 + 2").get_boolean_value());
+        assert!(parse_and_evaluate("\"abc\" <= \"abd\"").get_boolean_value());
+        assert_eq!(parse_and_evaluate("1 + 1 <=> 3").get_int_value(), Some(-1));
+    }
+
+    #[test]
+    fn test_expressions_parse_floor_division_and_power() {
+        assert_eq!(parse_and_evaluate("58 // 7").get_int_value(), Some(8));
+        assert_eq!(parse_and_evaluate("1 + 58 // 7 * 2").get_int_value(), Some(17));
+        assert_eq!(parse_and_evaluate("2 ** 3").get_int_value(), Some(8));
+        assert_eq!(parse_and_evaluate("2 ** 3 ** 2").get_int_value(), Some(512));
+        assert_eq!(parse_and_evaluate("3 * 2 ** 2").get_int_value(), Some(12));
+        assert_eq!(parse_and_evaluate("-2 ** 2").get_int_value(), Some(4));
+    }
+
+    #[test]
+    fn test_expressions_parse_concatenation() {
+        assert_eq!(parse_and_evaluate("\"Hello, \" ~ 'John'").get_string_value(), "Hello, John");
+        assert_eq!(parse_and_evaluate("\"Total: \" ~ 2 + 3").get_string_value(), "Total: 5");
+        assert!(parse_and_evaluate("1 ~ 2 == '12'").get_boolean_value());
+        assert_eq!(parse_and_evaluate("'3' + 4").get_int_value(), Some(7));
+        assert_eq!(parse_and_evaluate("-'2.5' * 2").get_float_value(), Some(-5.0));
+    }
+
+    #[test]
+    fn test_expressions_parse_filters() {
+        assert_eq!(parse_and_evaluate("' hi '|trim|upper").get_string_value(), "HI");
+        assert_eq!(parse_and_evaluate("'a' ~ 'b'|upper").get_string_value(), "aB");
+        assert_eq!(parse_and_evaluate("('a' ~ 'b')|upper").get_string_value(), "AB");
+        assert_eq!(parse_and_evaluate("-5|abs").get_int_value(), Some(-5));
+        assert_eq!(parse_and_evaluate("(-5)|abs + 1").get_int_value(), Some(6));
+        assert_eq!(parse_and_evaluate("max('abc'|length, 2)").get_int_value(), Some(3));
+        assert!(parse_and_evaluate("1 || 0").get_boolean_value());
+    }
+
+    #[test]
+    fn test_expressions_parse_filter_without_operand() {
+        match parse(String::from("1 + |upper"), &get_parsing_context()) {
+            Ok(_) => panic!("Expected an error, got a node"),
+            Err(e) => assert_eq!((e.offset, e.message.as_str()), (4, "Cannot parse the part of expression: \"|upper\"")),
+        };
+    }
+
     #[test]
     fn test_expresions_get_end_offset() {
         match get_end_offset(&String::from("2+3 }} hello"), "}}") {
@@ -163,5 +585,17 @@ mod tests {
             Some(offset) => assert_eq!(offset, 7),
             None => panic!("Expected number, got None")
         }
+
+        match get_end_offset(&String::from(" \"}}\" ~ '}}' }} hello"), "}}") {
+            Some(offset) => assert_eq!(offset, 14),
+            None => panic!("Expected number, got None")
+        }
+
+        match get_end_offset(&String::from(" {a: {b: 1}}|length }} hello"), "}}") {
+            Some(offset) => assert_eq!(offset, 21),
+            None => panic!("Expected number, got None")
+        }
+
+        assert_eq!(get_end_offset(&String::from(" \"}} hello"), "}}"), None);
     }
 }
\ No newline at end of file
@@ -1,7 +1,9 @@
 use crate::context::render_context::RenderContext;
 use crate::expressions::errors::evaluation_error::EvaluationError;
 use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult};
+use crate::expressions::nodes::{arithmetic, precedence};
 use crate::common::variable::Variable;
+use crate::context::parsing_context::ParsingContext;
 
 //// Arithmetic division
 pub struct Division {
@@ -16,7 +18,7 @@ impl Division {
     }
 }
 
-pub fn try_create_from_string(expression: String, _offset: usize) -> NodeCreateResult {
+pub fn try_create_from_string(expression: String, _offset: usize, _context: &ParsingContext) -> NodeCreateResult {
     let is_first_char_plus = match expression.chars().nth(0) {
         None => false,
         Some(c) => '/' == c
@@ -35,8 +37,8 @@ impl Node for Division {
                 Some(_) => Ok(()),
             }?;
         }
-        let operand1 = self.operands[0].as_ref().unwrap().evaluate(&context)?;
-        let operand2 = self.operands[1].as_ref().unwrap().evaluate(&context)?;
+        let operand1 = arithmetic::to_number(self.operands[0].as_ref().unwrap().evaluate(context)?);
+        let operand2 = arithmetic::to_number(self.operands[1].as_ref().unwrap().evaluate(context)?);
         let mut result = Variable::new_from_int(0);
         if operand1.get_float_value().is_some() && operand2.get_float_value().is_some() {
             result.set_float_value(operand1.get_float_value().unwrap() / operand2.get_float_value().unwrap());
@@ -58,6 +60,10 @@ impl Node for Division {
     fn get_type(&self) -> &str {
         "division"
     }
+
+    fn get_precedence(&self) -> usize {
+        precedence::MULTIPLICATIVE
+    }
 }
 
 #[cfg(test)]
@@ -68,7 +74,7 @@ mod tests {
 
     #[test]
     fn test_expressions_node_division_try_create_from_string_valid() {
-        match try_create_from_string(String::from("/ 2"), 0) {
+        match try_create_from_string(String::from("/ 2"), 0, &ParsingContext::new()) {
             NodeCreateResult::Some(_) => {},
             NodeCreateResult::None => panic!("Expected an operator, got None"),
             NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
@@ -77,7 +83,7 @@ mod tests {
 
     #[test]
     fn test_expressions_node_division_try_create_from_string_none() {
-        match try_create_from_string(String::from("+ 2"), 0) {
+        match try_create_from_string(String::from("+ 2"), 0, &ParsingContext::new()) {
             NodeCreateResult::Some(_) => panic!("Expected None, got Result"),
             NodeCreateResult::None => {},
             NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
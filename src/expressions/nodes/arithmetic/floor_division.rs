@@ -0,0 +1,148 @@
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult};
+use crate::expressions::nodes::{arithmetic, precedence};
+use crate::common::variable::Variable;
+use crate::context::parsing_context::ParsingContext;
+
+const SYMBOL_FORM: &str = "//";
+
+/// Division with a result floored toward negative infinity, e.g. `58 // 7 = 8`
+pub struct FloorDivision {
+    operands: BinaryOperands,
+}
+
+impl FloorDivision {
+    fn new() -> FloorDivision {
+        FloorDivision{
+            operands: [None, None],
+        }
+    }
+}
+
+pub fn try_create_from_string(expression: String, _offset: usize, _context: &ParsingContext) -> NodeCreateResult {
+    match expression.starts_with(SYMBOL_FORM) {
+        true => NodeCreateResult::Some((Box::new(FloorDivision::new()), SYMBOL_FORM.len())),
+        false => NodeCreateResult::None,
+    }
+}
+
+impl Node for FloorDivision {
+    fn evaluate(&self, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        for (i, operand) in self.operands.iter().enumerate() {
+            match operand {
+                None => Err(EvaluationError::new(format!("Operand with index '{}' is not defined", i))),
+                Some(_) => Ok(()),
+            }?;
+        }
+        let operand1 = arithmetic::to_number(self.operands[0].as_ref().unwrap().evaluate(context)?);
+        let operand2 = arithmetic::to_number(self.operands[1].as_ref().unwrap().evaluate(context)?);
+        if let (Some(dividend), Some(divisor)) = (operand1.get_int_value(), operand2.get_int_value()) {
+            if divisor == 0 {
+                return Err(EvaluationError::new(String::from("Division by zero")))
+            }
+            let quotient = match dividend.checked_div(divisor) {
+                Some(q) => q,
+                None => return Err(EvaluationError::new(format!("Cannot divide {} by {}", dividend, divisor))),
+            };
+            // integer division truncates toward zero. Step down when the exact result is negative
+            let is_floor_adjusted = dividend % divisor != 0 && ((dividend < 0) != (divisor < 0));
+            return Ok(Variable::new_from_int(if is_floor_adjusted { quotient - 1 } else { quotient }));
+        }
+
+        if let (Some(dividend), Some(divisor)) = (operand1.get_float_value(), operand2.get_float_value()) {
+            if divisor == 0.0 {
+                return Err(EvaluationError::new(String::from("Division by zero")))
+            }
+            let result = (dividend / divisor).floor();
+            if !result.is_finite() {
+                return Err(EvaluationError::new(format!("Cannot divide {} by {}", dividend, divisor)))
+            }
+            return Ok(Variable::new_from_int(result as i128));
+        }
+
+        Err(EvaluationError::new(String::from("Unsupported types of operands for floor division operator")))
+    }
+
+    fn is_operator(&self) -> bool {
+        true
+    }
+
+    fn set_binary_operands(&mut self, operands: BinaryOperands) {
+        self.operands = operands;
+    }
+
+    fn get_type(&self) -> &str {
+        "floor_division"
+    }
+
+    fn get_precedence(&self) -> usize {
+        precedence::MULTIPLICATIVE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::render_context::RenderContext;
+    use crate::expressions::nodes::general::literal::Literal;
+
+    fn evaluate(operand1: Literal, operand2: Literal) -> Result<Variable, EvaluationError> {
+        let mut operator = FloorDivision::new();
+        operator.set_binary_operands([Some(Box::from(operand1)), Some(Box::from(operand2))]);
+        operator.evaluate(&RenderContext::new())
+    }
+
+    #[test]
+    fn test_expressions_node_floor_division_try_create_from_string_valid() {
+        match try_create_from_string(String::from("// 2"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some((_, cursor)) => assert_eq!(cursor, 2),
+            NodeCreateResult::None => panic!("Expected an operator, got None"),
+            NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_expressions_node_floor_division_try_create_from_string_none() {
+        match try_create_from_string(String::from("/ 2"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some(_) => panic!("Expected None, got Result"),
+            NodeCreateResult::None => {},
+            NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_expressions_node_floor_division_ints() {
+        let cases = [(58, 7, 8), (-58, 7, -9), (58, -7, -9), (-58, -7, 8), (56, 7, 8), (-56, 7, -8)];
+        for (dividend, divisor, expected) in cases {
+            match evaluate(Literal::new_from_int(dividend), Literal::new_from_int(divisor)) {
+                Ok(p) => assert_eq!(p.get_int_value(), Some(expected)),
+                Err(e) => panic!("Expected an integer-type parameter, got an error: {}", e),
+            };
+        }
+    }
+
+    #[test]
+    fn test_expressions_node_floor_division_floats() {
+        match evaluate(Literal::new_from_float(7.5), Literal::new_from_int(2)) {
+            Ok(p) => assert_eq!(p.get_int_value(), Some(3)),
+            Err(e) => panic!("Expected an integer-type parameter, got an error: {}", e),
+        };
+        match evaluate(Literal::new_from_float(-7.5), Literal::new_from_int(2)) {
+            Ok(p) => assert_eq!(p.get_int_value(), Some(-4)),
+            Err(e) => panic!("Expected an integer-type parameter, got an error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_expressions_node_floor_division_by_zero() {
+        match evaluate(Literal::new_from_int(7), Literal::new_from_int(0)) {
+            Ok(_) => panic!("Expected an error, but got a parameter"),
+            Err(e) => assert_eq!(e.message, "Division by zero"),
+        };
+        match evaluate(Literal::new_from_str("Hello"), Literal::new_from_int(2)) {
+            Ok(_) => panic!("Expected an error, but got a parameter"),
+            Err(e) => assert_eq!(e.message, "Unsupported types of operands for floor division operator"),
+        };
+    }
+}
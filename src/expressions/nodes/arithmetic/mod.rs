@@ -1,12 +1,19 @@
 pub mod division;
+pub mod floor_division;
 pub mod multiplication;
 pub mod modulo;
+pub mod power;
 pub mod subtraction;
 pub mod sum;
 
 use crate::expressions::nodes::{NodeCreator, NodeCreateResult};
+use crate::common::variable::Variable;
+use crate::context::parsing_context::ParsingContext;
 
-const NODE_CREATORS: [NodeCreator; 5] = [
+const NODE_CREATORS: [NodeCreator; 7] = [
+    // IMPORTANT: "//" and "**" must be checked before "/" and "*"
+    floor_division::try_create_from_string,
+    power::try_create_from_string,
     division::try_create_from_string,
     modulo::try_create_from_string,
     multiplication::try_create_from_string,
@@ -14,9 +21,9 @@ const NODE_CREATORS: [NodeCreator; 5] = [
     sum::try_create_from_string,
 ];
 
-pub fn try_create_from_string(string_remain: String, offset: usize) -> NodeCreateResult {
+pub fn try_create_from_string(string_remain: String, offset: usize, context: &ParsingContext) -> NodeCreateResult {
     for node_creator in &NODE_CREATORS {
-        match node_creator(string_remain.clone(), offset) {
+        match node_creator(string_remain.clone(), offset, context) {
             NodeCreateResult::Some(r) => return NodeCreateResult::Some(r),
             NodeCreateResult::Err(e) => return NodeCreateResult::Err(e),
             NodeCreateResult::None => {},
@@ -24,3 +31,44 @@ pub fn try_create_from_string(string_remain: String, offset: usize) -> NodeCreat
     }
     NodeCreateResult::None
 }
+
+/// Converts a numeric string like `"42"` or `" 2.5 "` to a number, as arithmetic operators
+/// accept numeric strings in place of numbers. Other values are returned unchanged
+pub fn to_number(variable: Variable) -> Variable {
+    let string = match variable.get_type_name() {
+        "string" => variable.get_string_value(),
+        _ => return variable,
+    };
+    let string = string.trim();
+    let is_numeric = string.chars().any(|c| c.is_ascii_digit())
+        && string.chars().all(|c| c.is_ascii_digit() || "+-.eE".contains(c));
+    if !is_numeric {
+        return variable;
+    }
+    if let Ok(value) = string.parse::<i128>() {
+        Variable::new_from_int(value)
+    } else if let Ok(value) = string.parse::<f64>() {
+        Variable::new_from_float(value)
+    } else {
+        variable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expressions_node_arithmetic_to_number() {
+        assert_eq!(to_number(Variable::new_from_str("42")).get_int_value(), Some(42));
+        assert_eq!(to_number(Variable::new_from_str(" -7 ")).get_int_value(), Some(-7));
+        assert_eq!(to_number(Variable::new_from_str("2.5")).get_float_value(), Some(2.5));
+        assert_eq!(to_number(Variable::new_from_str("1e3")).get_float_value(), Some(1000.0));
+        assert_eq!(to_number(Variable::new_from_int(3)).get_int_value(), Some(3));
+        for string in ["", "abc", "12abc", "inf", "NaN", "1-2", "."].iter() {
+            let variable = to_number(Variable::new_from_str(string));
+            assert_eq!(variable.get_type_name(), "string");
+            assert_eq!(variable.get_string_value(), *string);
+        }
+    }
+}
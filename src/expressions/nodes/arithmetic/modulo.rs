@@ -1,7 +1,9 @@
 use crate::context::render_context::RenderContext;
 use crate::expressions::errors::evaluation_error::EvaluationError;
 use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult};
+use crate::expressions::nodes::{arithmetic, precedence};
 use crate::common::variable::Variable;
+use crate::context::parsing_context::ParsingContext;
 
 //// Modulo
 pub struct Modulo {
@@ -16,7 +18,7 @@ impl Modulo {
     }
 }
 
-pub fn try_create_from_string(expression: String, _offset: usize) -> NodeCreateResult {
+pub fn try_create_from_string(expression: String, _offset: usize, _context: &ParsingContext) -> NodeCreateResult {
     let is_first_char_plus = match expression.chars().nth(0) {
         None => false,
         Some(c) => '%' == c
@@ -35,8 +37,8 @@ impl Node for Modulo {
                 Some(_) => Ok(()),
             }?;
         }
-        let dividend = self.operands[0].as_ref().unwrap().evaluate(&context)?;
-        let divisor = self.operands[1].as_ref().unwrap().evaluate(&context)?;
+        let dividend = arithmetic::to_number(self.operands[0].as_ref().unwrap().evaluate(context)?);
+        let divisor = arithmetic::to_number(self.operands[1].as_ref().unwrap().evaluate(context)?);
         if dividend.get_int_value().is_none() {
             return Err(EvaluationError::new(format!("Dividend is not an integer: {}", dividend.get_string_value())))
         } else if divisor.get_int_value().is_none() {
@@ -65,6 +67,10 @@ impl Node for Modulo {
     fn get_type(&self) -> &str {
         "modulo"
     }
+
+    fn get_precedence(&self) -> usize {
+        precedence::MULTIPLICATIVE
+    }
 }
 
 #[cfg(test)]
@@ -75,7 +81,7 @@ mod tests {
 
     #[test]
     fn test_expressions_node_modulo_try_create_from_string_valid() {
-        match try_create_from_string(String::from("% 2"), 0) {
+        match try_create_from_string(String::from("% 2"), 0, &ParsingContext::new()) {
             NodeCreateResult::Some(_) => {},
             NodeCreateResult::None => panic!("Expected an operator, got None"),
             NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
@@ -84,7 +90,7 @@ mod tests {
 
     #[test]
     fn test_expressions_node_modulo_try_create_from_string_none() {
-        match try_create_from_string(String::from("+ 2"), 0) {
+        match try_create_from_string(String::from("+ 2"), 0, &ParsingContext::new()) {
             NodeCreateResult::Some(_) => panic!("Expected None, got Result"),
             NodeCreateResult::None => {},
             NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
@@ -0,0 +1,151 @@
+use std::convert::TryFrom;
+
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::nodes::{Associativity, BinaryOperands, Node, NodeCreateResult};
+use crate::expressions::nodes::{arithmetic, precedence};
+use crate::common::variable::Variable;
+use crate::context::parsing_context::ParsingContext;
+
+const SYMBOL_FORM: &str = "**";
+
+/// Exponentiation, e.g. `2 ** 3 = 8`
+pub struct Power {
+    operands: BinaryOperands,
+}
+
+impl Power {
+    fn new() -> Power {
+        Power{
+            operands: [None, None],
+        }
+    }
+}
+
+pub fn try_create_from_string(expression: String, _offset: usize, _context: &ParsingContext) -> NodeCreateResult {
+    match expression.starts_with(SYMBOL_FORM) {
+        true => NodeCreateResult::Some((Box::new(Power::new()), SYMBOL_FORM.len())),
+        false => NodeCreateResult::None,
+    }
+}
+
+impl Node for Power {
+    fn evaluate(&self, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        for (i, operand) in self.operands.iter().enumerate() {
+            match operand {
+                None => Err(EvaluationError::new(format!("Operand with index '{}' is not defined", i))),
+                Some(_) => Ok(()),
+            }?;
+        }
+        let base = arithmetic::to_number(self.operands[0].as_ref().unwrap().evaluate(context)?);
+        let exponent = arithmetic::to_number(self.operands[1].as_ref().unwrap().evaluate(context)?);
+        // an integer result is kept while it fits. Negative exponents and overflows promote to float
+        if let (Some(b), Some(e)) = (base.get_int_value(), exponent.get_int_value()) {
+            let int_result = match u32::try_from(e) {
+                Ok(e) => b.checked_pow(e),
+                Err(_) => None,
+            };
+            if let Some(result) = int_result {
+                return Ok(Variable::new_from_int(result));
+            }
+        }
+
+        match (base.get_float_value(), exponent.get_float_value()) {
+            (Some(b), Some(e)) => Ok(Variable::new_from_float(b.powf(e))),
+            _ => Err(EvaluationError::new(String::from("Unsupported types of operands for power operator"))),
+        }
+    }
+
+    fn is_operator(&self) -> bool {
+        true
+    }
+
+    fn set_binary_operands(&mut self, operands: BinaryOperands) {
+        self.operands = operands;
+    }
+
+    fn get_type(&self) -> &str {
+        "power"
+    }
+
+    fn get_precedence(&self) -> usize {
+        precedence::POWER
+    }
+
+    fn get_associativity(&self) -> Associativity {
+        Associativity::Right
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::render_context::RenderContext;
+    use crate::expressions::nodes::general::literal::Literal;
+
+    fn evaluate(operand1: Literal, operand2: Literal) -> Result<Variable, EvaluationError> {
+        let mut operator = Power::new();
+        operator.set_binary_operands([Some(Box::from(operand1)), Some(Box::from(operand2))]);
+        operator.evaluate(&RenderContext::new())
+    }
+
+    #[test]
+    fn test_expressions_node_power_try_create_from_string_valid() {
+        match try_create_from_string(String::from("** 2"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some((_, cursor)) => assert_eq!(cursor, 2),
+            NodeCreateResult::None => panic!("Expected an operator, got None"),
+            NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_expressions_node_power_try_create_from_string_none() {
+        match try_create_from_string(String::from("* 2"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some(_) => panic!("Expected None, got Result"),
+            NodeCreateResult::None => {},
+            NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_expressions_node_power_ints() {
+        match evaluate(Literal::new_from_int(2), Literal::new_from_int(3)) {
+            Ok(p) => assert_eq!(p.get_int_value(), Some(8)),
+            Err(e) => panic!("Expected an integer-type parameter, got an error: {}", e),
+        };
+        match evaluate(Literal::new_from_int(-3), Literal::new_from_int(0)) {
+            Ok(p) => assert_eq!(p.get_int_value(), Some(1)),
+            Err(e) => panic!("Expected an integer-type parameter, got an error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_expressions_node_power_promotes_to_float() {
+        match evaluate(Literal::new_from_int(2), Literal::new_from_int(-2)) {
+            Ok(p) => {
+                assert_eq!(p.get_int_value(), None);
+                assert_eq!(p.get_float_value(), Some(0.25));
+            },
+            Err(e) => panic!("Expected a float-type parameter, got an error: {}", e),
+        };
+        match evaluate(Literal::new_from_int(10), Literal::new_from_int(40)) {
+            Ok(p) => {
+                assert_eq!(p.get_int_value(), None);
+                assert_eq!(p.get_float_value(), Some(1e40));
+            },
+            Err(e) => panic!("Expected a float-type parameter, got an error: {}", e),
+        };
+        match evaluate(Literal::new_from_float(4.0), Literal::new_from_float(0.5)) {
+            Ok(p) => assert_eq!(p.get_float_value(), Some(2.0)),
+            Err(e) => panic!("Expected a float-type parameter, got an error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_expressions_node_power_string_int() {
+        match evaluate(Literal::new_from_str("Hello"), Literal::new_from_int(2)) {
+            Ok(_) => panic!("Expected an error, but got a parameter"),
+            Err(e) => assert_eq!(e.message, "Unsupported types of operands for power operator"),
+        };
+    }
+}
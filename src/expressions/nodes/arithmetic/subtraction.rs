@@ -1,7 +1,9 @@
 use crate::context::render_context::RenderContext;
 use crate::expressions::errors::evaluation_error::EvaluationError;
 use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult};
+use crate::expressions::nodes::{arithmetic, precedence};
 use crate::common::variable::Variable;
+use crate::context::parsing_context::ParsingContext;
 
 //// Arithmetic subtraction
 pub struct Subtraction {
@@ -16,7 +18,7 @@ impl Subtraction {
     }
 }
 
-pub fn try_create_from_string(expression: String, _offset: usize) -> NodeCreateResult {
+pub fn try_create_from_string(expression: String, _offset: usize, _context: &ParsingContext) -> NodeCreateResult {
     let is_first_char_minus = match expression.chars().nth(0) {
         None => false,
         Some(c) => '-' == c
@@ -35,11 +37,15 @@ impl Node for Subtraction {
                 Some(_) => Ok(()),
             }?;
         }
-        let operand1 = self.operands[0].as_ref().unwrap().evaluate(&context)?;
-        let operand2 = self.operands[1].as_ref().unwrap().evaluate(&context)?;
+        let operand1 = arithmetic::to_number(self.operands[0].as_ref().unwrap().evaluate(context)?);
+        let operand2 = arithmetic::to_number(self.operands[1].as_ref().unwrap().evaluate(context)?);
         let mut result = Variable::new_from_int(0);
         if operand1.get_int_value().is_some() && operand2.get_int_value().is_some() {
-            result.set_int_value(operand1.get_int_value().unwrap() - operand2.get_int_value().unwrap());
+            let (value1, value2) = (operand1.get_int_value().unwrap(), operand2.get_int_value().unwrap());
+            match value1.checked_sub(value2) {
+                Some(value) => result.set_int_value(value),
+                None => return Err(EvaluationError::new(format!("Integer overflow: {} - {}", value1, value2))),
+            }
         } else if operand1.get_float_value().is_some() && operand2.get_float_value().is_some() {
             result.set_float_value(operand1.get_float_value().unwrap() - operand2.get_float_value().unwrap());
         } else {
@@ -61,6 +67,10 @@ impl Node for Subtraction {
     fn get_type(&self) -> &str {
         "subtraction"
     }
+
+    fn get_precedence(&self) -> usize {
+        precedence::ADDITIVE
+    }
 }
 
 #[cfg(test)]
@@ -71,7 +81,7 @@ mod tests {
 
     #[test]
     fn test_expressions_node_subtraction_try_create_from_string_valid() {
-        match try_create_from_string(String::from("- 2"), 0) {
+        match try_create_from_string(String::from("- 2"), 0, &ParsingContext::new()) {
             NodeCreateResult::Some(_) => {},
             NodeCreateResult::None => panic!("Expected an operator, got None"),
             NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
@@ -80,7 +90,7 @@ mod tests {
 
     #[test]
     fn test_expressions_node_subtraction_try_create_from_string_none() {
-        match try_create_from_string(String::from("+ 2"), 0) {
+        match try_create_from_string(String::from("+ 2"), 0, &ParsingContext::new()) {
             NodeCreateResult::Some(_) => panic!("Expected None, got Result"),
             NodeCreateResult::None => {},
             NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
@@ -128,4 +138,18 @@ mod tests {
         };
         assert_eq!(err.message, "Unsupported types of operands for subtraction operator");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_expressions_node_subtraction_int_overflow() {
+        let mut operator = Subtraction::new();
+        operator.set_binary_operands([
+            Some(Box::from(Literal::new_from_int(i128::MIN))),
+            Some(Box::from(Literal::new_from_int(1))),
+        ]);
+        let err = match operator.evaluate(&RenderContext::new()) {
+            Ok(p) => panic!("Expected an error, but got a value: {:?}", p),
+            Err(e) => e,
+        };
+        assert_eq!(err.message, "Integer overflow: -170141183460469231731687303715884105728 - 1");
+    }
+}
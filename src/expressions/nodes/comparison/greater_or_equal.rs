@@ -0,0 +1,107 @@
+use std::cmp::Ordering;
+
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult};
+use crate::expressions::nodes::comparison::{compare, evaluate_operands};
+use crate::expressions::nodes::precedence;
+use crate::common::variable::Variable;
+use crate::context::parsing_context::ParsingContext;
+
+const SYMBOL_FORM: &str = ">=";
+
+/// "Greater than or equal to" comparison, e.g. `a >= b`
+pub struct GreaterOrEqual {
+    operands: BinaryOperands,
+}
+
+impl GreaterOrEqual {
+    fn new() -> GreaterOrEqual {
+        GreaterOrEqual{
+            operands: [None, None],
+        }
+    }
+}
+
+pub fn try_create_from_string(expression: String, _offset: usize, _context: &ParsingContext) -> NodeCreateResult {
+    match expression.starts_with(SYMBOL_FORM) {
+        true => NodeCreateResult::Some((Box::new(GreaterOrEqual::new()), SYMBOL_FORM.len())),
+        false => NodeCreateResult::None,
+    }
+}
+
+impl Node for GreaterOrEqual {
+    fn evaluate(&self, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        let (operand1, operand2) = evaluate_operands(&self.operands, context)?;
+        Ok(Variable::new_from_boolean(compare(&operand1, &operand2)? != Ordering::Less))
+    }
+
+    fn is_operator(&self) -> bool {
+        true
+    }
+
+    fn set_binary_operands(&mut self, operands: BinaryOperands) {
+        self.operands = operands;
+    }
+
+    fn get_type(&self) -> &str {
+        "greater_or_equal"
+    }
+
+    fn get_precedence(&self) -> usize {
+        precedence::COMPARISON
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::render_context::RenderContext;
+    use crate::expressions::nodes::general::literal::Literal;
+
+    #[test]
+    fn test_expressions_node_comparison_greater_or_equal_try_create_from_string_valid() {
+        match try_create_from_string(String::from(">= 2"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some((_, cursor)) => assert_eq!(cursor, 2),
+            NodeCreateResult::None => panic!("Expected an operator, got None"),
+            NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_expressions_node_comparison_greater_or_equal_try_create_from_string_none() {
+        match try_create_from_string(String::from("> 2"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some(_) => panic!("Expected None, got Result"),
+            NodeCreateResult::None => {},
+            NodeCreateResult::Err(e) => panic!("Expected None, got an error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_expressions_node_comparison_greater_or_equal_evaluate_first() {
+        let mut operator = GreaterOrEqual::new();
+        operator.set_binary_operands([
+            Some(Box::from(Literal::new_from_int(3))),
+            Some(Box::from(Literal::new_from_int(3))),
+        ]);
+        let param = match operator.evaluate(&RenderContext::new()) {
+            Ok(p) => p,
+            Err(e) => panic!("Expected a Boolean-type parameter, got an error: {}", e),
+        };
+        assert!(param.get_boolean_value());
+    }
+
+    #[test]
+    fn test_expressions_node_comparison_greater_or_equal_evaluate_second() {
+        let mut operator = GreaterOrEqual::new();
+        operator.set_binary_operands([
+            Some(Box::from(Literal::new_from_str("a"))),
+            Some(Box::from(Literal::new_from_str("ab"))),
+        ]);
+        let param = match operator.evaluate(&RenderContext::new()) {
+            Ok(p) => p,
+            Err(e) => panic!("Expected a Boolean-type parameter, got an error: {}", e),
+        };
+        assert!(!param.get_boolean_value());
+    }
+}
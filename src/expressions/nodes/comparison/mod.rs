@@ -0,0 +1,166 @@
+pub mod equal;
+pub mod greater;
+pub mod greater_or_equal;
+pub mod less;
+pub mod less_or_equal;
+pub mod not_equal;
+pub mod spaceship;
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::nodes::arithmetic;
+use crate::expressions::nodes::{BinaryOperands, NodeCreator, NodeCreateResult};
+use crate::common::variable::Variable;
+use crate::context::parsing_context::ParsingContext;
+
+const NODE_CREATORS: [NodeCreator; 7] = [
+    // IMPORTANT: longer operators must be checked first ("<=>" before "<=" before "<")
+    spaceship::try_create_from_string,
+    less_or_equal::try_create_from_string,
+    less::try_create_from_string,
+    greater_or_equal::try_create_from_string,
+    greater::try_create_from_string,
+    equal::try_create_from_string,
+    not_equal::try_create_from_string,
+];
+
+pub fn try_create_from_string(string_remain: String, offset: usize, context: &ParsingContext) -> NodeCreateResult {
+    for node_creator in &NODE_CREATORS {
+        match node_creator(string_remain.clone(), offset, context) {
+            NodeCreateResult::Some(r) => return NodeCreateResult::Some(r),
+            NodeCreateResult::Err(e) => return NodeCreateResult::Err(e),
+            NodeCreateResult::None => {},
+        }
+    }
+    NodeCreateResult::None
+}
+
+/// Compares two variables.
+/// Numbers are compared by value (integers and floats are comparable with each other),
+/// strings are compared lexicographically and booleans as `false < true`.
+/// A numeric string is compared with a number as a number, like in arithmetic: `5 == "5"`.
+/// Other combinations of types are incomparable
+pub fn compare(left: &Variable, right: &Variable) -> Result<Ordering, EvaluationError> {
+    if is_number(left) != is_number(right) {
+        let (l, r) = (arithmetic::to_number(left.clone()), arithmetic::to_number(right.clone()));
+        if is_number(&l) && is_number(&r) {
+            return compare(&l, &r);
+        }
+    }
+    let ordering = if let (Some(l), Some(r)) = (left.get_int_value(), right.get_int_value()) {
+        Some(l.cmp(&r))
+    } else if let (Some(l), Some(r)) = (left.get_float_value(), right.get_float_value()) {
+        l.partial_cmp(&r)
+    } else {
+        match (left.get_type_name(), right.get_type_name()) {
+            ("string", "string") => Some(left.get_string_value().cmp(&right.get_string_value())),
+            ("boolean", "boolean") => Some(left.get_boolean_value().cmp(&right.get_boolean_value())),
+            _ => None,
+        }
+    };
+    match ordering {
+        Some(o) => Ok(o),
+        None => Err(EvaluationError::new(format!("Cannot compare values of types \"{}\" and \"{}\": {} and {}",
+            left.get_type_name(), right.get_type_name(), left.get_string_value(), right.get_string_value()))),
+    }
+}
+
+fn is_number(variable: &Variable) -> bool {
+    matches!(variable, Variable::Int(_) | Variable::Float(_))
+}
+
+/// Checks two variables for equality. Lists and maps are equal if their items are equal,
+/// `null` is only equal to `null`, objects are equal if they are the same instance. Other incomparable values are never equal
+pub fn is_equal(left: &Variable, right: &Variable) -> bool {
+    match (left, right) {
+        (Variable::Null, Variable::Null) => true,
+        (Variable::Object(l), Variable::Object(r)) => Arc::ptr_eq(l, r),
+        (Variable::List(l), Variable::List(r)) => l.len() == r.len()
+            && l.iter().zip(r.iter()).all(|(a, b)| is_equal(a, b)),
+        (Variable::Map(l), Variable::Map(r)) => l.len() == r.len()
+            && l.iter().all(|(k, v)| r.get(k).is_some_and(|other| is_equal(v, other))),
+        _ => match compare(left, right) {
+            Ok(ordering) => ordering == Ordering::Equal,
+            Err(_) => false,
+        },
+    }
+}
+
+// Evaluates both operands of a comparison operator
+fn evaluate_operands(operands: &BinaryOperands, context: &RenderContext) -> Result<(Variable, Variable), EvaluationError> {
+    for (i, operand) in operands.iter().enumerate() {
+        match operand {
+            None => Err(EvaluationError::new(format!("Operand with index '{}' is not defined", i))),
+            Some(_) => Ok(()),
+        }?;
+    }
+    let operand1 = operands[0].as_ref().unwrap().evaluate(context)?;
+    let operand2 = operands[1].as_ref().unwrap().evaluate(context)?;
+    Ok((operand1, operand2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::variable::VariableStore;
+
+    #[test]
+    fn test_expressions_node_comparison_compare_numbers() {
+        assert_eq!(compare(&Variable::new_from_int(2), &Variable::new_from_int(3)).ok(), Some(Ordering::Less));
+        assert_eq!(compare(&Variable::new_from_int(3), &Variable::new_from_float(2.5)).ok(), Some(Ordering::Greater));
+        assert_eq!(compare(&Variable::new_from_float(3.0), &Variable::new_from_int(3)).ok(), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn test_expressions_node_comparison_compare_strings_booleans() {
+        assert_eq!(compare(&Variable::new_from_str("abc"), &Variable::new_from_str("abd")).ok(), Some(Ordering::Less));
+        assert_eq!(compare(&Variable::new_from_str("b"), &Variable::new_from_str("abc")).ok(), Some(Ordering::Greater));
+        assert_eq!(compare(&Variable::new_from_boolean(false), &Variable::new_from_boolean(true)).ok(), Some(Ordering::Less));
+    }
+
+    #[test]
+    fn test_expressions_node_comparison_compare_incomparable() {
+        let err = match compare(&Variable::new_from_str("abc"), &Variable::new_from_int(1)) {
+            Ok(_) => panic!("Expected an error, got an ordering"),
+            Err(e) => e,
+        };
+        assert_eq!(err.message, "Cannot compare values of types \"string\" and \"int\": abc and 1");
+        assert!(compare(&Variable::new_from_float(f64::NAN), &Variable::new_from_int(1)).is_err());
+        assert!(!is_equal(&Variable::new_from_str("1a"), &Variable::new_from_int(1)));
+    }
+
+    #[test]
+    fn test_expressions_node_comparison_compare_numeric_strings() {
+        assert_eq!(compare(&Variable::new_from_int(5), &Variable::new_from_str("5")).ok(), Some(Ordering::Equal));
+        assert_eq!(compare(&Variable::new_from_str(" 2.5 "), &Variable::new_from_int(3)).ok(), Some(Ordering::Less));
+        assert_eq!(compare(&Variable::new_from_str("10"), &Variable::new_from_float(9.5)).ok(), Some(Ordering::Greater));
+        assert!(is_equal(&Variable::new_from_str("1"), &Variable::new_from_int(1)));
+        assert!(is_equal(&Variable::new_from_float(1.0), &Variable::new_from_str("1")));
+        // Two strings are compared as strings even if they are numeric
+        assert_eq!(compare(&Variable::new_from_str("10"), &Variable::new_from_str("9")).ok(), Some(Ordering::Less));
+        assert!(!is_equal(&Variable::new_from_str("1.0"), &Variable::new_from_str("1")));
+        assert!(compare(&Variable::new_from_str("abc"), &Variable::new_from_int(1)).is_err());
+        assert!(compare(&Variable::new_from_boolean(true), &Variable::new_from_str("1")).is_err());
+    }
+
+    #[test]
+    fn test_expressions_node_comparison_is_equal_collections() {
+        let list = |items: Vec<i32>| Variable::from(items);
+        assert!(is_equal(&list(vec![1, 2]), &list(vec![1, 2])));
+        assert!(!is_equal(&list(vec![1, 2]), &list(vec![2, 1])));
+        assert!(is_equal(&Variable::new_from_list(vec![Variable::new_from_int(1)]), &Variable::new_from_list(vec![Variable::new_from_float(1.0)])));
+
+        let mut left = VariableStore::new();
+        left.insert(String::from("a"), Variable::new_from_int(1));
+        left.insert(String::from("b"), Variable::new_null());
+        let mut right = VariableStore::new();
+        right.insert(String::from("b"), Variable::new_null());
+        right.insert(String::from("a"), Variable::new_from_int(1));
+        assert!(is_equal(&Variable::new_from_map(left), &Variable::new_from_map(right)));
+        assert!(is_equal(&Variable::new_null(), &Variable::new_null()));
+        assert!(!is_equal(&Variable::new_null(), &Variable::new_from_str("")));
+    }
+}
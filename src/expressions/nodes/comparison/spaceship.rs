@@ -0,0 +1,128 @@
+use std::cmp::Ordering;
+
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult};
+use crate::expressions::nodes::comparison::{compare, evaluate_operands};
+use crate::expressions::nodes::precedence;
+use crate::common::variable::Variable;
+use crate::context::parsing_context::ParsingContext;
+
+const SYMBOL_FORM: &str = "<=>";
+
+/// Combined comparison, e.g. `a <=> b`. Returns -1, 0 or 1
+pub struct Spaceship {
+    operands: BinaryOperands,
+}
+
+impl Spaceship {
+    fn new() -> Spaceship {
+        Spaceship{
+            operands: [None, None],
+        }
+    }
+}
+
+pub fn try_create_from_string(expression: String, _offset: usize, _context: &ParsingContext) -> NodeCreateResult {
+    match expression.starts_with(SYMBOL_FORM) {
+        true => NodeCreateResult::Some((Box::new(Spaceship::new()), SYMBOL_FORM.len())),
+        false => NodeCreateResult::None,
+    }
+}
+
+impl Node for Spaceship {
+    fn evaluate(&self, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        let (operand1, operand2) = evaluate_operands(&self.operands, context)?;
+        let result = match compare(&operand1, &operand2)? {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        };
+
+        Ok(Variable::new_from_int(result))
+    }
+
+    fn is_operator(&self) -> bool {
+        true
+    }
+
+    fn set_binary_operands(&mut self, operands: BinaryOperands) {
+        self.operands = operands;
+    }
+
+    fn get_type(&self) -> &str {
+        "spaceship"
+    }
+
+    fn get_precedence(&self) -> usize {
+        precedence::COMPARISON
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::render_context::RenderContext;
+    use crate::expressions::nodes::general::literal::Literal;
+
+    #[test]
+    fn test_expressions_node_comparison_spaceship_try_create_from_string_valid() {
+        match try_create_from_string(String::from("<=> 2"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some((_, cursor)) => assert_eq!(cursor, 3),
+            NodeCreateResult::None => panic!("Expected an operator, got None"),
+            NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_expressions_node_comparison_spaceship_try_create_from_string_none() {
+        match try_create_from_string(String::from("<= 2"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some(_) => panic!("Expected None, got Result"),
+            NodeCreateResult::None => {},
+            NodeCreateResult::Err(e) => panic!("Expected None, got an error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_expressions_node_comparison_spaceship_evaluate() {
+        for (left, right, expected) in [(1, 2, -1), (2, 2, 0), (3, 2, 1)] {
+            let mut operator = Spaceship::new();
+            operator.set_binary_operands([
+                Some(Box::from(Literal::new_from_int(left))),
+                Some(Box::from(Literal::new_from_int(right))),
+            ]);
+            let param = match operator.evaluate(&RenderContext::new()) {
+                Ok(p) => p,
+                Err(e) => panic!("Expected an integer-type parameter, got an error: {}", e),
+            };
+            assert_eq!(param.get_int_value(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_expressions_node_comparison_spaceship_numeric_string() {
+        let mut operator = Spaceship::new();
+        operator.set_binary_operands([
+            Some(Box::from(Literal::new_from_int(5))),
+            Some(Box::from(Literal::new_from_str("5"))),
+        ]);
+        match operator.evaluate(&RenderContext::new()) {
+            Ok(p) => assert_eq!(p.get_int_value(), Some(0)),
+            Err(e) => panic!("Expected an integer-type parameter, got an error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_expressions_node_comparison_spaceship_incomparable() {
+        let mut operator = Spaceship::new();
+        operator.set_binary_operands([
+            Some(Box::from(Literal::new_from_str("abc"))),
+            Some(Box::from(Literal::new_from_bool(true))),
+        ]);
+        let err = match operator.evaluate(&RenderContext::new()) {
+            Ok(_) => panic!("Expected an error, but got a parameter"),
+            Err(e) => e,
+        };
+        assert_eq!(err.message, "Cannot compare values of types \"string\" and \"boolean\": abc and true");
+    }
+}
@@ -0,0 +1,95 @@
+use crate::context::parsing_context::ParsingContext;
+use crate::context::render_context::RenderContext;
+use crate::expressions;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::errors::parsing_error::ParsingError;
+use crate::expressions::functions::NamedArgs;
+use crate::expressions::nodes::Node;
+use crate::common::variable::Variable;
+
+/// Positional and named arguments of a function or filter call, e.g. `(value, precision=2)`
+pub struct Arguments {
+    args: Vec<Box<dyn Node>>,
+    named_args: Vec<(String, Box<dyn Node>)>,
+}
+
+impl Arguments {
+    pub fn new() -> Arguments {
+        Arguments {
+            args: Vec::new(),
+            named_args: Vec::new(),
+        }
+    }
+
+    /// Parses arguments without brackets: "arg1, arg2, name=value".
+    /// Named arguments go after positional ones
+    pub fn parse(arguments: &str, offset: usize, context: &ParsingContext) -> Result<Arguments, ParsingError> {
+        let mut result = Arguments::new();
+        for (item_pos, item) in expressions::split_list_items(arguments, offset)? {
+            let item_offset = offset + item_pos;
+            match get_argument_name(item) {
+                Some((name, value_pos)) => {
+                    let value = expressions::parse_sub_expression(&item[value_pos..], item_offset + value_pos, context)?;
+                    result.named_args.push((String::from(name), value));
+                },
+                None => {
+                    if !result.named_args.is_empty() {
+                        let arg_offset = item_offset + item.len() - item.trim_start().len();
+                        return Err(ParsingError::new(arg_offset,
+                            String::from("Positional arguments cannot follow named arguments")));
+                    }
+                    result.args.push(expressions::parse_sub_expression(item, item_offset, context)?);
+                },
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn evaluate(&self, context: &RenderContext) -> Result<(Vec<Variable>, NamedArgs), EvaluationError> {
+        let mut args: Vec<Variable> = Vec::new();
+        for arg in self.args.iter() {
+            args.push(context.resolve_nested(arg.evaluate(context)?)?);
+        }
+        let mut named_args = NamedArgs::new();
+        for (name, arg) in self.named_args.iter() {
+            named_args.insert(name.clone(), context.resolve_nested(arg.evaluate(context)?)?);
+        }
+        Ok((args, named_args))
+    }
+}
+
+impl Default for Arguments {
+    fn default() -> Arguments {
+        Arguments::new()
+    }
+}
+
+// Returns a name of a named argument and a position of its value: " precision=2" -> ("precision", 11)
+fn get_argument_name(item: &str) -> Option<(&str, usize)> {
+    let name_start = item.len() - item.trim_start().len();
+    let name_end = name_start + item[name_start..].find(|c: char| !(c.is_alphanumeric() || '_' == c))?;
+    if name_end == name_start || !item[name_start..].starts_with(char::is_alphabetic) {
+        return None;
+    }
+    let equals_pos = name_end + item[name_end..].len() - item[name_end..].trim_start().len();
+    let after_name = &item[equals_pos..];
+    if after_name.starts_with('=') && !after_name.starts_with("==") {
+        Some((&item[name_start..name_end], equals_pos + 1))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expressions_nodes_general_arguments_get_argument_name() {
+        assert_eq!(get_argument_name(" precision=2"), Some(("precision", 11)));
+        assert_eq!(get_argument_name("size = 2"), Some(("size", 6)));
+        assert_eq!(get_argument_name("size == 2"), None);
+        assert_eq!(get_argument_name(" 'a=b'"), None);
+        assert_eq!(get_argument_name("size"), None);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::context::parsing_context::ParsingContext;
+use crate::context::render_context::RenderContext;
+use crate::expressions;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::errors::parsing_error::ParsingError;
+use crate::expressions::functions::Function;
+use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult};
+use crate::expressions::nodes::general::arguments::Arguments;
+use crate::common::variable::Variable;
+
+/// A call of a registered function, e.g. `max(a, b)` or `format(value, precision=2)`
+pub struct FunctionCall {
+    name: String,
+    function: Function,
+    arguments: Arguments,
+}
+
+impl FunctionCall {
+    pub fn new(name: String, function: Function, arguments: Arguments) -> FunctionCall {
+        FunctionCall {
+            name,
+            function,
+            arguments,
+        }
+    }
+}
+
+pub fn try_create_from_string(expression: String, offset: usize, context: &ParsingContext) -> NodeCreateResult {
+    let is_first_char_alphabetic = match expression.chars().next() {
+        Some(c) => c.is_alphabetic(),
+        None => false,
+    };
+    if !is_first_char_alphabetic {
+        return NodeCreateResult::None;
+    }
+    let name_end = match expression.find(|c: char| !(c.is_alphanumeric() || '_' == c)) {
+        Some(i) => i,
+        None => return NodeCreateResult::None,
+    };
+    if !expression[name_end..].starts_with('(') {
+        return NodeCreateResult::None;
+    }
+
+    let name = &expression[..name_end];
+    let function = match context.functions.get(name) {
+        Some(f) => *f,
+        None => return NodeCreateResult::Err(ParsingError::new(offset, format!("Unknown function \"{}\"", name))),
+    };
+    let end_pos = match expressions::find_closing_bracket(&expression[name_end..], offset + name_end) {
+        Ok(p) => name_end + p,
+        Err(e) => return NodeCreateResult::Err(e),
+    };
+    let arguments = match Arguments::parse(&expression[name_end+1..end_pos], offset + name_end + 1, context) {
+        Ok(a) => a,
+        Err(e) => return NodeCreateResult::Err(e),
+    };
+    let node = FunctionCall::new(String::from(name), function, arguments);
+    NodeCreateResult::Some((Box::new(node), end_pos + 1))
+}
+
+impl Node for FunctionCall {
+    fn evaluate(&self, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        let (args, named_args) = self.arguments.evaluate(context)?;
+        match (self.function)(&args, &named_args, context) {
+            Ok(v) => Ok(v),
+            Err(e) => Err(EvaluationError::new(format!("Function \"{}\" failed: {}", self.name, e.message))),
+        }
+    }
+
+    fn is_operator(&self) -> bool {
+        false
+    }
+
+    fn set_binary_operands(&mut self, _operands: BinaryOperands) {
+
+    }
+
+    fn get_type(&self) -> &str {
+        "function call"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::functions::NamedArgs;
+
+    fn describe(args: &[Variable], named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+        let mut names: Vec<&String> = named_args.keys().collect();
+        names.sort();
+        let args: Vec<String> = args.iter().map(|a| a.get_string_value()).collect();
+        let names: Vec<String> = names.iter().map(|n| format!("{}={}", n, named_args[*n].get_string_value())).collect();
+        Ok(Variable::new_from_string(format!("{}|{}", args.join(","), names.join(","))))
+    }
+
+    fn get_context() -> ParsingContext {
+        let mut context = ParsingContext::new();
+        context.functions.insert(String::from("describe"), describe);
+        context
+    }
+
+    fn call(expression: &str) -> (String, usize) {
+        let (node, cursor) = match try_create_from_string(String::from(expression), 0, &get_context()) {
+            NodeCreateResult::Some(result) => result,
+            NodeCreateResult::Err(e) => panic!("Exprected a result, got an error: {}", e),
+            NodeCreateResult::None => panic!("Exprected a result, got None"),
+        };
+        match node.evaluate(&RenderContext::new()) {
+            Ok(p) => (p.get_string_value(), cursor),
+            Err(e) => panic!("Expected a parameter, got an error: {}", e),
+        }
+    }
+
+    fn call_error(expression: &str) -> ParsingError {
+        match try_create_from_string(String::from(expression), 10, &get_context()) {
+            NodeCreateResult::Some(_) => panic!("Exprected an error, but got a result"),
+            NodeCreateResult::Err(e) => e,
+            NodeCreateResult::None => panic!("Exprected an error, got None"),
+        }
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_function_call_arguments() {
+        assert_eq!(call("describe() ~ 'x'"), (String::from("|"), 10));
+        assert_eq!(call("describe(1, 2 + 3, 'a,b')"), (String::from("1,5,a,b|"), 25));
+        assert_eq!(call("describe(1, size = 2, color='red',)"), (String::from("1|color=red,size=2"), 35));
+        assert_eq!(call("describe(1 == 1, describe(2))").0, "true,2||");
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_function_call_not_a_call() {
+        match try_create_from_string(String::from("describe + 1"), 0, &get_context()) {
+            NodeCreateResult::None => {},
+            _ => panic!("Exprected None"),
+        }
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_function_call_errors() {
+        let err = call_error("unknown(1)");
+        assert_eq!((err.offset, err.message.as_str()), (10, "Unknown function \"unknown\""));
+        let err = call_error("describe(a=1, 2)");
+        assert_eq!((err.offset, err.message.as_str()), (24, "Positional arguments cannot follow named arguments"));
+        let err = call_error("describe(1, 2");
+        assert_eq!((err.offset, err.message.as_str()), (18, "Bracket is not closed"));
+    }
+}
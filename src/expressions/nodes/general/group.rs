@@ -0,0 +1,104 @@
+use crate::context::render_context::RenderContext;
+use crate::expressions;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::errors::parsing_error::ParsingError;
+use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult};
+use crate::common::variable::Variable;
+use crate::context::parsing_context::ParsingContext;
+
+/// A sub-expression in parentheses, e.g. `(price + tax)`
+pub struct Group {
+    node: Box<dyn Node>,
+}
+
+impl Group {
+    pub fn new(node: Box<dyn Node>) -> Group {
+        Group {
+            node,
+        }
+    }
+}
+
+pub fn try_create_from_string(expression: String, offset: usize, context: &ParsingContext) -> NodeCreateResult {
+    if !expression.starts_with('(') {
+        return NodeCreateResult::None;
+    }
+    let end_pos = match expressions::find_closing_bracket(&expression, offset) {
+        Ok(p) => p,
+        Err(e) => return NodeCreateResult::Err(e),
+    };
+    let inner_expression = &expression[1..end_pos];
+    if inner_expression.trim().is_empty() {
+        return NodeCreateResult::Err(ParsingError::new(offset, String::from("Empty expression in brackets")));
+    }
+    match expressions::parse_sub_expression(inner_expression, offset + 1, context) {
+        Ok(node) => NodeCreateResult::Some((Box::new(Group::new(node)), end_pos + 1)),
+        Err(e) => NodeCreateResult::Err(e),
+    }
+}
+
+impl Node for Group {
+    fn evaluate(&self, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        self.node.evaluate(context)
+    }
+
+    fn is_operator(&self) -> bool {
+        false
+    }
+
+    fn set_binary_operands(&mut self, _operands: BinaryOperands) {
+
+    }
+
+    fn get_type(&self) -> &str {
+        "group"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expressions_nodes_general_group_correct() {
+        let result = match try_create_from_string(String::from("(2 + 3) * 4"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some(result) => result,
+            NodeCreateResult::Err(e) => panic!("Exprected a result, got an error: {}", e),
+            NodeCreateResult::None => panic!("Exprected a result, got None"),
+        };
+        let (node, cursor) = result;
+        assert_eq!(cursor, 7);
+        let param = match node.evaluate(&RenderContext::new()) {
+            Ok(p) => p,
+            Err(e) => panic!("Expected a parameter, got an error: {}", e),
+        };
+        assert_eq!(param.get_int_value(), Some(5));
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_group_not_closed() {
+        match try_create_from_string(String::from("(2 + (3 * 4)"), 5, &ParsingContext::new()) {
+            NodeCreateResult::Some(_) => panic!("Exprected an error, but got a result"),
+            NodeCreateResult::Err(e) => assert_eq!(e.offset, 5),
+            NodeCreateResult::None => panic!("Exprected an error, got None"),
+        }
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_group_empty() {
+        match try_create_from_string(String::from("( ) + 1"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some(_) => panic!("Exprected an error, but got a result"),
+            NodeCreateResult::Err(e) => assert_eq!(e.message, "Empty expression in brackets"),
+            NodeCreateResult::None => panic!("Exprected an error, got None"),
+        }
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_group_not_group() {
+        match try_create_from_string(String::from("2 + 3"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some(_) => panic!("Exprected None, but got a result"),
+            NodeCreateResult::Err(e) => panic!("Exprected None, but got an error: {}", e),
+            NodeCreateResult::None => {},
+        }
+    }
+}
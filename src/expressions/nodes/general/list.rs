@@ -0,0 +1,98 @@
+use crate::context::parsing_context::ParsingContext;
+use crate::context::render_context::RenderContext;
+use crate::expressions;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::errors::parsing_error::ParsingError;
+use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult};
+use crate::common::variable::Variable;
+
+/// A list literal, e.g. `[1, user.name, "three"]`. A trailing comma is allowed
+pub struct List {
+    items: Vec<Box<dyn Node>>,
+}
+
+impl List {
+    pub fn new(items: Vec<Box<dyn Node>>) -> List {
+        List {
+            items,
+        }
+    }
+}
+
+pub fn try_create_from_string(expression: String, offset: usize, context: &ParsingContext) -> NodeCreateResult {
+    if !expression.starts_with('[') {
+        return NodeCreateResult::None;
+    }
+    match parse_items(&expression, offset, context) {
+        Ok((items, end_pos)) => NodeCreateResult::Some((Box::new(List::new(items)), end_pos + 1)),
+        Err(e) => NodeCreateResult::Err(e),
+    }
+}
+
+// Returns parsed items and a position of the closing bracket
+fn parse_items(expression: &str, offset: usize, context: &ParsingContext) -> Result<(Vec<Box<dyn Node>>, usize), ParsingError> {
+    let end_pos = expressions::find_closing_bracket(expression, offset)?;
+    let mut items: Vec<Box<dyn Node>> = Vec::new();
+    for (item_pos, item) in expressions::split_list_items(&expression[1..end_pos], offset + 1)? {
+        items.push(expressions::parse_sub_expression(item, offset + 1 + item_pos, context)?);
+    }
+    Ok((items, end_pos))
+}
+
+impl Node for List {
+    fn evaluate(&self, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        let mut list: Vec<Variable> = Vec::new();
+        for item in self.items.iter() {
+            list.push(item.evaluate(context)?);
+        }
+        Ok(Variable::new_from_list(list))
+    }
+
+    fn is_operator(&self) -> bool {
+        false
+    }
+
+    fn set_binary_operands(&mut self, _operands: BinaryOperands) {
+
+    }
+
+    fn get_type(&self) -> &str {
+        "list"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(expression: &str) -> (String, usize) {
+        let (node, cursor) = match try_create_from_string(String::from(expression), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some(result) => result,
+            NodeCreateResult::Err(e) => panic!("Exprected a result, got an error: {}", e),
+            NodeCreateResult::None => panic!("Exprected a result, got None"),
+        };
+        match node.evaluate(&RenderContext::new()) {
+            Ok(p) => (p.get_string_value(), cursor),
+            Err(e) => panic!("Expected a parameter, got an error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_list() {
+        assert_eq!(evaluate("[1, 'two', 1 + 2] ~ 'x'"), (String::from("[1, \"two\", 3]"), 17));
+        assert_eq!(evaluate("[]"), (String::from("[]"), 2));
+        assert_eq!(evaluate("[[1, 2], [],]"), (String::from("[[1, 2], []]"), 13));
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_list_errors() {
+        match try_create_from_string(String::from("[1, , 2]"), 10, &ParsingContext::new()) {
+            NodeCreateResult::Err(e) => assert_eq!((e.offset, e.message.as_str()), (14, "Unexpected comma")),
+            _ => panic!("Exprected an error"),
+        };
+        match try_create_from_string(String::from("[1, 2"), 10, &ParsingContext::new()) {
+            NodeCreateResult::Err(e) => assert_eq!((e.offset, e.message.as_str()), (10, "Bracket is not closed")),
+            _ => panic!("Exprected an error"),
+        };
+    }
+}
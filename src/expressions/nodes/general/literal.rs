@@ -1,8 +1,16 @@
+use std::convert::TryFrom;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
 use crate::context::render_context::RenderContext;
+use crate::expressions;
 use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult};
+use crate::expressions::nodes::general::group::Group;
+use crate::expressions::nodes::string::concatenation::Concatenation;
 use crate::expressions::errors::evaluation_error::EvaluationError;
 use crate::expressions::errors::parsing_error::ParsingError;
 use crate::common::variable::Variable;
+use crate::context::parsing_context::ParsingContext;
 
 //// A literal (string, number, etc) which needs no further evaluation
 pub struct Literal {
@@ -24,6 +32,10 @@ impl Literal {
         Literal::new(Variable::new_from_str(string))
     }
 
+    pub fn new_from_string(string: String) -> Literal {
+        Literal::new(Variable::new_from_string(string))
+    }
+
     pub fn new_from_int(value: i128) -> Literal {
         Literal::new(Variable::new_from_int(value))
     }
@@ -33,7 +45,7 @@ impl Literal {
     }
 }
 
-pub fn try_create_from_string(expression: String, offset: usize) -> NodeCreateResult {
+pub fn try_create_from_string(expression: String, offset: usize, context: &ParsingContext) -> NodeCreateResult {
     let c = expression.chars().nth(0);
     if c.is_none() {
         return NodeCreateResult::None;
@@ -41,41 +53,251 @@ pub fn try_create_from_string(expression: String, offset: usize) -> NodeCreateRe
     let c = c.unwrap();
     if c.is_digit(10) {
         return try_create_numeric_literal(expression.clone(), offset);
-    } else if c == '"' {
-        return try_create_string_literal(expression.clone(), offset);
+    } else if c == '"' || c == '\'' {
+        return try_create_string_literal(expression.clone(), offset, context);
     }
-    NodeCreateResult::None
+    try_create_keyword_literal(&expression)
 }
 
+// Parses keywords `true`, `false`, `null` and its alias `none`
+fn try_create_keyword_literal(expression: &str) -> NodeCreateResult {
+    let end_pos = expression.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(expression.len());
+    let value = match &expression[..end_pos] {
+        "true" => Variable::new_from_boolean(true),
+        "false" => Variable::new_from_boolean(false),
+        "null" | "none" => Variable::new_null(),
+        _ => return NodeCreateResult::None,
+    };
+    NodeCreateResult::Some((Box::new(Literal::new(value)), end_pos))
+}
+
+// Parses integers (decimal, hexadecimal "0x", octal "0o", binary "0b") and floats ("3.14", "1e6", "1.5E-3").
+// Digits may be separated with underscores: "10_000"
 fn try_create_numeric_literal(expression: String, offset: usize) -> NodeCreateResult {
-    let mut last_digit_index: usize = 0;
-    loop {
-        match expression.chars().nth(last_digit_index+1) {
-            Some(char_at_index) => {
-                if char_at_index.is_digit(10) {
-                    last_digit_index += 1;
-                } else {
-                    break;
-                }
+    match parse_numeric_literal(&expression, offset, false) {
+        Ok((variable, cursor)) => NodeCreateResult::Some((Box::new(Literal::new(variable)), cursor)),
+        Err(e) => NodeCreateResult::Err(e),
+    }
+}
+
+/// Parses the smallest integer after a minus sign, e.g. "170141183460469231731687303715884105728" of
+/// `-170141183460469231731687303715884105728`. Its magnitude does not fit into i128 unless the sign is applied.
+/// Other numbers are left to the negation operator, which binds weaker than filters and subscripts
+pub fn try_create_min_int_literal(expression: &str, offset: usize) -> Option<(Box<dyn Node>, usize)> {
+    if !expression.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    match parse_numeric_literal(expression, offset, true) {
+        Ok((Variable::Int(i128::MIN), cursor)) => Some((Box::new(Literal::new_from_int(i128::MIN)), cursor)),
+        _ => None,
+    }
+}
+
+fn parse_numeric_literal(expression: &str, offset: usize, is_negative: bool) -> Result<(Variable, usize), ParsingError> {
+    let bytes = expression.as_bytes();
+    let radix = match (bytes.first(), bytes.get(1)) {
+        (Some(b'0'), Some(b'x')) | (Some(b'0'), Some(b'X')) => 16,
+        (Some(b'0'), Some(b'o')) | (Some(b'0'), Some(b'O')) => 8,
+        (Some(b'0'), Some(b'b')) | (Some(b'0'), Some(b'B')) => 2,
+        _ => 10,
+    };
+    if radix != 10 {
+        let (digits, end_pos) = scan_digits(expression, 2, radix, offset)?;
+        if digits.is_empty() {
+            return Err(ParsingError::new(offset + 2, format!("Expected digits after the prefix \"{}\"", &expression[..2])));
+        }
+        check_numeric_literal_end(expression, end_pos, offset)?;
+        return match parse_int(&digits, radix, is_negative) {
+            Some(n) => Ok((Variable::new_from_int(n), end_pos)),
+            None => Err(ParsingError::new(offset, format!("Integer literal is out of range: {}", &expression[..end_pos]))),
+        };
+    }
+
+    let (mut number, mut end_pos) = scan_digits(expression, 0, 10, offset)?;
+    let mut is_float = false;
+    let is_fraction = bytes.get(end_pos) == Some(&b'.') && bytes.get(end_pos + 1).is_some_and(u8::is_ascii_digit);
+    if is_fraction {
+        let (fraction, fraction_end_pos) = scan_digits(expression, end_pos + 1, 10, offset)?;
+        number = format!("{}.{}", number, fraction);
+        end_pos = fraction_end_pos;
+        is_float = true;
+    }
+    if bytes.get(end_pos) == Some(&b'e') || bytes.get(end_pos) == Some(&b'E') {
+        let mut exponent_pos = end_pos + 1;
+        let sign = match bytes.get(exponent_pos) {
+            Some(b'+') | Some(b'-') => {
+                exponent_pos += 1;
+                &expression[exponent_pos-1..exponent_pos]
             },
-            None => { break; }
+            _ => "",
         };
+        let (exponent, exponent_end_pos) = scan_digits(expression, exponent_pos, 10, offset)?;
+        if exponent.is_empty() {
+            return Err(ParsingError::new(offset + exponent_pos, String::from("Expected digits in the exponent")));
+        }
+        number = format!("{}e{}{}", number, sign, exponent);
+        end_pos = exponent_end_pos;
+        is_float = true;
+    }
+    check_numeric_literal_end(expression, end_pos, offset)?;
+
+    if is_float {
+        match number.parse::<f64>() {
+            Ok(n) if n.is_finite() => Ok((Variable::new_from_float(if is_negative { -n } else { n }), end_pos)),
+            _ => Err(ParsingError::new(offset, format!("Float literal is out of range: {}", &expression[..end_pos]))),
+        }
+    } else {
+        match parse_int(&number, 10, is_negative) {
+            Some(n) => Ok((Variable::new_from_int(n), end_pos)),
+            None => Err(ParsingError::new(offset, format!("Integer literal is out of range: {}", &expression[..end_pos]))),
+        }
+    }
+}
+
+// The sign is applied before the range check, so that i128::MIN is accepted
+fn parse_int(digits: &str, radix: u32, is_negative: bool) -> Option<i128> {
+    let magnitude = u128::from_str_radix(digits, radix).ok()?;
+    match is_negative {
+        true => 0i128.checked_sub_unsigned(magnitude),
+        false => i128::try_from(magnitude).ok(),
     }
-    let number = expression[0..last_digit_index+1].to_string();
-    match number.parse::<i32>() {
-        Ok(n) => NodeCreateResult::Some((Box::new(Literal::new_from_int(n.into())), last_digit_index + 1)),
-        Err(e) => NodeCreateResult::Err(ParsingError::new(offset, format!("Cannot convert char \"{}\" to integer: {}", offset, e))),
+}
+
+// Reads digits starting at start_pos. Returns the digits without separators and the position after the last digit
+fn scan_digits(expression: &str, start_pos: usize, radix: u32, offset: usize) -> Result<(String, usize), ParsingError> {
+    let mut digits = String::new();
+    let mut pos = start_pos;
+    for c in expression[start_pos..].chars() {
+        if c == '_' {
+            let is_next_digit = expression[pos+1..].chars().next().is_some_and(|n| n.is_digit(radix));
+            if digits.is_empty() || !is_next_digit {
+                return Err(ParsingError::new(offset + pos, String::from("Digit separator \"_\" must be placed between digits")));
+            }
+        } else if c.is_digit(radix) {
+            digits.push(c);
+        } else {
+            break;
+        }
+        pos += 1;
+    }
+    Ok((digits, pos))
+}
+
+// A numeric literal must not be immediately followed by letters or digits, e.g. "12abc" or "0b102"
+fn check_numeric_literal_end(expression: &str, end_pos: usize, offset: usize) -> Result<(), ParsingError> {
+    let next_char = expression[end_pos..].chars().next();
+    let next_char_after = expression[end_pos..].chars().nth(1);
+    let is_invalid = match next_char {
+        Some('.') => next_char_after.is_some_and(|c| c.is_ascii_digit()),
+        Some(c) => c.is_alphanumeric() || c == '_',
+        None => false,
+    };
+    match (is_invalid, next_char) {
+        (true, Some(c)) => Err(ParsingError::new(offset + end_pos, format!("Unexpected character \"{}\" in numeric literal", c))),
+        _ => Ok(()),
     }
 }
 
-fn try_create_string_literal(expression: String, offset: usize) -> NodeCreateResult {
-    let exp = expression[1..].to_string();
-    match exp.find("\"") {
-        Some(pos) => {
-            let string = &exp[..pos];
-            NodeCreateResult::Some((Box::new(Literal::new_from_str(&string)), pos+2)) // pos+2: +1 per each quote
-        },
-        None => NodeCreateResult::Err(ParsingError::new(offset, format!("String is not closed"))),
+// Parses strings in double or single quotes. Supports escape sequences ("\n", "\u{1F600}", etc)
+// and interpolation of expressions in double-quoted strings: "Hello, #{user}!"
+fn try_create_string_literal(expression: String, offset: usize, context: &ParsingContext) -> NodeCreateResult {
+    let end_pos = match expressions::find_closing_quote(&expression) {
+        Some(p) => p,
+        None => return NodeCreateResult::Err(ParsingError::new(offset, String::from("String is not closed"))),
+    };
+    let is_interpolation_allowed = expression.starts_with('"');
+    match parse_string_contents(&expression[1..end_pos], offset + 1, is_interpolation_allowed, context) {
+        Ok(node) => NodeCreateResult::Some((node, end_pos + 1)),
+        Err(e) => NodeCreateResult::Err(e),
+    }
+}
+
+// Builds a literal or, if the string has interpolations, a concatenation of its parts
+fn parse_string_contents(contents: &str, offset: usize, is_interpolation_allowed: bool, context: &ParsingContext) -> Result<Box<dyn Node>, ParsingError> {
+    let mut parts: Vec<Box<dyn Node>> = Vec::new();
+    let mut string = String::new();
+    let mut chars = contents.char_indices().peekable();
+    while let Some((pos, c)) = chars.next() {
+        match c {
+            '\\' => {
+                let escaped_char = match chars.next() {
+                    Some((_, 'u')) => parse_unicode_escape(pos, offset, &mut chars)?,
+                    Some((escaped_pos, escaped)) => match get_escaped_char(escaped) {
+                        Some(e) => e,
+                        None => return Err(ParsingError::new(offset + escaped_pos,
+                            format!("Unknown escape sequence \"\\{}\"", escaped))),
+                    },
+                    None => return Err(ParsingError::new(offset + pos, String::from("Incomplete escape sequence"))),
+                };
+                string.push(escaped_char);
+            },
+            '#' if is_interpolation_allowed && chars.peek().map(|(_, n)| *n) == Some('{') => {
+                let end_pos = pos + 1 + expressions::find_closing_bracket(&contents[pos+1..], offset + pos + 1)?;
+                let node = expressions::parse_sub_expression(&contents[pos+2..end_pos], offset + pos + 2, context)?;
+                if !string.is_empty() || parts.is_empty() {
+                    parts.push(Box::new(Literal::new_from_string(string)));
+                    string = String::new();
+                }
+                parts.push(node);
+                while let Some((p, _)) = chars.peek() {
+                    if *p > end_pos {
+                        break;
+                    }
+                    chars.next();
+                }
+            },
+            _ => string.push(c),
+        }
+    }
+    if parts.is_empty() {
+        return Ok(Box::new(Literal::new_from_string(string)));
+    }
+    if !string.is_empty() {
+        parts.push(Box::new(Literal::new_from_string(string)));
+    }
+
+    let mut parts = parts.into_iter();
+    let mut node = parts.next().unwrap();
+    for part in parts {
+        let mut concatenation = Concatenation::new();
+        concatenation.set_binary_operands([Some(node), Some(part)]);
+        node = Box::new(concatenation);
+    }
+    // An interpolated string is an operand for the enclosing expression
+    Ok(Box::new(Group::new(node)))
+}
+
+fn get_escaped_char(c: char) -> Option<char> {
+    match c {
+        'n' => Some('\n'),
+        'r' => Some('\r'),
+        't' => Some('\t'),
+        'v' => Some('\u{0B}'),
+        'f' => Some('\u{0C}'),
+        'e' => Some('\u{1B}'),
+        '0' => Some('\0'),
+        '\\' | '"' | '\'' | '#' => Some(c),
+        _ => None,
+    }
+}
+
+// Parses "\u{XXXX}". The backslash is at escape_pos, "u" is already consumed
+fn parse_unicode_escape(escape_pos: usize, offset: usize, chars: &mut Peekable<CharIndices>) -> Result<char, ParsingError> {
+    let error = ParsingError::new(offset + escape_pos, String::from("Invalid unicode escape sequence. Expected: \\u{XXXX}"));
+    if chars.next().map(|(_, c)| c) != Some('{') {
+        return Err(error);
+    }
+    let mut code = String::new();
+    loop {
+        match chars.next() {
+            Some((_, '}')) => break,
+            Some((_, c)) if c.is_ascii_hexdigit() && code.len() < 6 => code.push(c),
+            _ => return Err(error),
+        }
+    }
+    match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+        Some(c) => Ok(c),
+        None => Err(error),
     }
 }
 
@@ -96,3 +318,151 @@ impl Node for Literal {
         "literal"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::render_context::RenderContext;
+
+    fn parse_number(expression: &str) -> (Variable, usize) {
+        let (node, cursor) = match try_create_from_string(String::from(expression), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some(result) => result,
+            NodeCreateResult::Err(e) => panic!("Exprected a result, got an error: {}", e),
+            NodeCreateResult::None => panic!("Exprected a result, got None"),
+        };
+        match node.evaluate(&RenderContext::new()) {
+            Ok(p) => (p, cursor),
+            Err(e) => panic!("Expected a parameter, got an error: {}", e),
+        }
+    }
+
+    fn parse_number_error(expression: &str) -> ParsingError {
+        match try_create_from_string(String::from(expression), 10, &ParsingContext::new()) {
+            NodeCreateResult::Some(_) => panic!("Exprected an error, but got a result"),
+            NodeCreateResult::Err(e) => e,
+            NodeCreateResult::None => panic!("Exprected an error, got None"),
+        }
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_literal_integers() {
+        let (param, cursor) = parse_number("123 + 4");
+        assert_eq!(param.get_int_value(), Some(123));
+        assert_eq!(cursor, 3);
+        assert_eq!(parse_number("10_000").0.get_int_value(), Some(10000));
+        assert_eq!(parse_number("3000000000").0.get_int_value(), Some(3000000000));
+        assert_eq!(parse_number("170141183460469231731687303715884105727").0.get_int_value(), Some(i128::MAX));
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_literal_keywords() {
+        let (value, cursor) = parse_number("true and x");
+        assert_eq!(value, Variable::new_from_boolean(true));
+        assert_eq!(cursor, 4);
+        assert_eq!(parse_number("false").0, Variable::new_from_boolean(false));
+        assert_eq!(parse_number("null").0, Variable::new_null());
+        assert_eq!(parse_number("none").0, Variable::new_null());
+        assert!(matches!(try_create_from_string(String::from("nullable"), 0, &ParsingContext::new()), NodeCreateResult::None));
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_literal_prefixed_integers() {
+        assert_eq!(parse_number("0xFF").0.get_int_value(), Some(255));
+        assert_eq!(parse_number("0o17").0.get_int_value(), Some(15));
+        assert_eq!(parse_number("0b1010_1010").0.get_int_value(), Some(170));
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_literal_floats() {
+        let (param, cursor) = parse_number("2.25)");
+        assert_eq!(param.get_int_value(), None);
+        assert_eq!(param.get_float_value(), Some(2.25));
+        assert_eq!(cursor, 4);
+        assert_eq!(parse_number("1e6").0.get_float_value(), Some(1000000.0));
+        assert_eq!(parse_number("1.5E-3").0.get_float_value(), Some(0.0015));
+        assert_eq!(parse_number("2e+2").0.get_float_value(), Some(200.0));
+        assert_eq!(parse_number("1_000.000_1").0.get_float_value(), Some(1000.0001));
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_literal_dot_without_fraction() {
+        let (param, cursor) = parse_number("1.name");
+        assert_eq!(param.get_int_value(), Some(1));
+        assert_eq!(cursor, 1);
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_literal_malformed_numbers() {
+        let err = parse_number_error("12abc");
+        assert_eq!(err.offset, 12);
+        assert_eq!(err.message, "Unexpected character \"a\" in numeric literal");
+        assert_eq!(parse_number_error("1__0").offset, 11);
+        assert_eq!(parse_number_error("10_").offset, 12);
+        assert_eq!(parse_number_error("1.2.3").offset, 13);
+        assert_eq!(parse_number_error("0x").offset, 12);
+        assert_eq!(parse_number_error("0b102").offset, 14);
+        let err = parse_number_error("1e+");
+        assert_eq!(err.offset, 13);
+        assert_eq!(err.message, "Expected digits in the exponent");
+        let err = parse_number_error("170141183460469231731687303715884105728");
+        assert_eq!(err.offset, 10);
+        assert_eq!(err.message, "Integer literal is out of range: 170141183460469231731687303715884105728");
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_literal_min_int() {
+        let (node, cursor) = try_create_min_int_literal("170141183460469231731687303715884105728 + 1", 0).unwrap();
+        assert_eq!(node.evaluate(&RenderContext::new()).ok(), Some(Variable::new_from_int(i128::MIN)));
+        assert_eq!(cursor, 39);
+        assert!(try_create_min_int_literal("0x8000_0000_0000_0000_0000_0000_0000_0000", 0).is_some());
+        assert!(try_create_min_int_literal("170141183460469231731687303715884105727", 0).is_none());
+        assert!(try_create_min_int_literal("170141183460469231731687303715884105729", 0).is_none());
+        assert!(try_create_min_int_literal("x", 0).is_none());
+    }
+
+    fn parse_string(expression: &str) -> (String, usize) {
+        let (node, cursor) = match try_create_from_string(String::from(expression), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some(result) => result,
+            NodeCreateResult::Err(e) => panic!("Exprected a result, got an error: {}", e),
+            NodeCreateResult::None => panic!("Exprected a result, got None"),
+        };
+        let mut context = RenderContext::new();
+        context.set_parameter("name", Variable::new_from_str("John"));
+        match node.evaluate(&context) {
+            Ok(p) => (p.get_string_value(), cursor),
+            Err(e) => panic!("Expected a parameter, got an error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_literal_strings() {
+        assert_eq!(parse_string("\"hello\" ~ x"), (String::from("hello"), 7));
+        assert_eq!(parse_string("'hello' ~ x"), (String::from("hello"), 7));
+        assert_eq!(parse_string("'it\\'s'"), (String::from("it's"), 7));
+        assert_eq!(parse_string("\"say \\\"hi\\\"\""), (String::from("say \"hi\""), 12));
+        assert_eq!(parse_string("\"a\\nb\\t\\\\\"").0, "a\nb\t\\");
+        assert_eq!(parse_string("\"\\u{48}\\u{1F600}\"").0, "H\u{1F600}");
+        assert_eq!(parse_string("\"%} }}\"").0, "%} }}");
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_literal_string_interpolation() {
+        assert_eq!(parse_string("\"Hello, #{name}!\"").0, "Hello, John!");
+        assert_eq!(parse_string("\"#{ 2 * 3 }\"").0, "6");
+        assert_eq!(parse_string("\"#{name}#{\"}\"}\"").0, "John}");
+        assert_eq!(parse_string("\"\\#{name}\"").0, "#{name}");
+        assert_eq!(parse_string("'#{name}'").0, "#{name}");
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_literal_malformed_strings() {
+        let err = parse_number_error("\"abc");
+        assert_eq!(err.offset, 10);
+        assert_eq!(err.message, "String is not closed");
+        let err = parse_number_error("\"ab\\qc\"");
+        assert_eq!(err.offset, 14);
+        assert_eq!(err.message, "Unknown escape sequence \"\\q\"");
+        assert_eq!(parse_number_error("\"\\u{ZZ}\"").offset, 11);
+        assert_eq!(parse_number_error("\"a #{1 + } b\"").offset, 19);
+    }
+}
\ No newline at end of file
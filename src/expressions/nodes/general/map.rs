@@ -0,0 +1,171 @@
+use crate::context::parsing_context::ParsingContext;
+use crate::context::render_context::RenderContext;
+use crate::expressions;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::errors::parsing_error::ParsingError;
+use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult};
+use crate::expressions::nodes::general::variable::Variable as VariableNode;
+use crate::common::variable::{Variable, VariableStore};
+
+enum MapKey {
+    /// `{ label: 1 }`, `{ "label": 1 }` or `{ 2: 1 }`
+    Static(String),
+    /// `{ (prefix ~ "label"): 1 }`
+    Computed(Box<dyn Node>),
+}
+
+type MapItem = (MapKey, Box<dyn Node>);
+
+/// A map literal, e.g. `{ "label": "OK", cls: "btn", (key): value, name }`.
+/// `{ name }` is a shorthand for `{ name: name }`
+pub struct Map {
+    items: Vec<MapItem>,
+}
+
+impl Map {
+    fn new(items: Vec<MapItem>) -> Map {
+        Map {
+            items,
+        }
+    }
+}
+
+pub fn try_create_from_string(expression: String, offset: usize, context: &ParsingContext) -> NodeCreateResult {
+    if !expression.starts_with('{') {
+        return NodeCreateResult::None;
+    }
+    match parse_items(&expression, offset, context) {
+        Ok((items, end_pos)) => NodeCreateResult::Some((Box::new(Map::new(items)), end_pos + 1)),
+        Err(e) => NodeCreateResult::Err(e),
+    }
+}
+
+// Returns parsed items and a position of the closing bracket
+fn parse_items(expression: &str, offset: usize, context: &ParsingContext) -> Result<(Vec<MapItem>, usize), ParsingError> {
+    let end_pos = expressions::find_closing_bracket(expression, offset)?;
+    let mut items: Vec<MapItem> = Vec::new();
+    for (item_pos, item) in expressions::split_list_items(&expression[1..end_pos], offset + 1)? {
+        let item_offset = offset + 1 + item_pos;
+        let item = match expressions::find_top_level_char(item, b':', item_offset)? {
+            Some(colon_pos) => (
+                parse_key(&item[..colon_pos], item_offset, context)?,
+                expressions::parse_sub_expression(&item[colon_pos+1..], item_offset + colon_pos + 1, context)?,
+            ),
+            None => {
+                let name = item.trim();
+                let name_offset = item_offset + item.len() - item.trim_start().len();
+                if !is_name(name) {
+                    return Err(ParsingError::new(name_offset,
+                        String::from("Expected a key and a value separated by \":\" or a variable name")));
+                }
+                (MapKey::Static(String::from(name)), Box::new(VariableNode::new_at(String::from(name), name_offset)) as Box<dyn Node>)
+            },
+        };
+        items.push(item);
+    }
+    Ok((items, end_pos))
+}
+
+fn parse_key(key: &str, offset: usize, context: &ParsingContext) -> Result<MapKey, ParsingError> {
+    let key_offset = offset + key.len() - key.trim_start().len();
+    let key = key.trim();
+    if is_name(key) || (!key.is_empty() && key.chars().all(|c| c.is_ascii_digit())) {
+        return Ok(MapKey::Static(String::from(key)));
+    }
+    let is_enclosed = match key.chars().next() {
+        Some('"') | Some('\'') => expressions::find_closing_quote(key) == Some(key.len() - 1),
+        Some('(') => expressions::find_closing_bracket(key, key_offset).ok() == Some(key.len() - 1),
+        _ => false,
+    };
+    if !is_enclosed {
+        return Err(ParsingError::new(key_offset,
+            String::from("A key must be a name, a string, an integer or an expression in parentheses")));
+    }
+    Ok(MapKey::Computed(expressions::parse_sub_expression(key, key_offset, context)?))
+}
+
+fn is_name(string: &str) -> bool {
+    string.starts_with(|c: char| c.is_alphabetic() || '_' == c) && string.chars().all(|c| c.is_alphanumeric() || '_' == c)
+}
+
+impl Node for Map {
+    fn evaluate(&self, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        let mut store = VariableStore::new();
+        for (key, value) in self.items.iter() {
+            let key = match key {
+                MapKey::Static(k) => k.clone(),
+                MapKey::Computed(k) => k.evaluate(context)?.get_string_value(),
+            };
+            store.insert(key, value.evaluate(context)?);
+        }
+        Ok(Variable::new_from_struct(store))
+    }
+
+    fn is_operator(&self) -> bool {
+        false
+    }
+
+    fn set_binary_operands(&mut self, _operands: BinaryOperands) {
+
+    }
+
+    fn get_type(&self) -> &str {
+        "map"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(expression: &str) -> (String, usize) {
+        let (node, cursor) = match try_create_from_string(String::from(expression), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some(result) => result,
+            NodeCreateResult::Err(e) => panic!("Exprected a result, got an error: {}", e),
+            NodeCreateResult::None => panic!("Exprected a result, got None"),
+        };
+        let mut context = RenderContext::new();
+        context.set_parameter("name", Variable::new_from_str("John"));
+        match node.evaluate(&context) {
+            Ok(p) => (p.get_string_value(), cursor),
+            Err(e) => panic!("Expected a parameter, got an error: {}", e),
+        }
+    }
+
+    fn parse_error(expression: &str) -> ParsingError {
+        match try_create_from_string(String::from(expression), 10, &ParsingContext::new()) {
+            NodeCreateResult::Some(_) => panic!("Exprected an error, but got a result"),
+            NodeCreateResult::Err(e) => e,
+            NodeCreateResult::None => panic!("Exprected an error, got None"),
+        }
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_map() {
+        assert_eq!(evaluate("{ \"label\": \"OK\", 'cls': \"btn\" } ~ 'x'"), (String::from("{\"label\": \"OK\", \"cls\": \"btn\"}"), 31));
+        assert_eq!(evaluate("{}"), (String::from("{}"), 2));
+        assert_eq!(evaluate("{ size: 1 + 1, 2: [1], }"), (String::from("{\"size\": 2, \"2\": [1]}"), 24));
+        assert_eq!(evaluate("{ ('a' ~ 'b'): { c: 'd:e' } }"), (String::from("{\"ab\": {\"c\": \"d:e\"}}"), 29));
+        assert_eq!(evaluate("{ name }"), (String::from("{\"name\": \"John\"}"), 8));
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_map_errors() {
+        let err = parse_error("{ 'a' ~ 'b': 1 }");
+        assert_eq!((err.offset, err.message.as_str()), (12, "A key must be a name, a string, an integer or an expression in parentheses"));
+        let err = parse_error("{ a: 1, 'b' }");
+        assert_eq!((err.offset, err.message.as_str()), (18, "Expected a key and a value separated by \":\" or a variable name"));
+        let err = parse_error("{ a: }");
+        assert_eq!(err.offset, 14);
+    }
+
+    #[test]
+    fn test_expressions_nodes_general_map_shorthand_undefined() {
+        let (node, _) = match try_create_from_string(String::from("{ a: 1, missing }"), 10, &ParsingContext::new()) {
+            NodeCreateResult::Some(result) => result,
+            _ => panic!("Exprected a result"),
+        };
+        let err = node.evaluate(&RenderContext::new()).err().unwrap();
+        assert_eq!((err.offset, err.message.as_str()), (Some(18), "Variable not found: missing"));
+    }
+}
@@ -1,16 +1,26 @@
+pub mod arguments;
+pub mod function_call;
+pub mod group;
+pub mod list;
 pub mod literal;
+pub mod map;
 pub mod variable;
 
 use crate::expressions::nodes::{NodeCreator, NodeCreateResult};
+use crate::context::parsing_context::ParsingContext;
 
-const NODE_CREATORS: [NodeCreator; 2] = [
+const NODE_CREATORS: [NodeCreator; 6] = [
+    function_call::try_create_from_string,
+    group::try_create_from_string,
+    list::try_create_from_string,
     literal::try_create_from_string,
+    map::try_create_from_string,
     variable::try_create_from_string,
 ];
 
-pub fn try_create_from_string(string_remain: String, offset: usize) -> NodeCreateResult {
+pub fn try_create_from_string(string_remain: String, offset: usize, context: &ParsingContext) -> NodeCreateResult {
     for node_creator in &NODE_CREATORS {
-        match node_creator(string_remain.clone(), offset) {
+        match node_creator(string_remain.clone(), offset, context) {
             NodeCreateResult::Some(r) => return NodeCreateResult::Some(r),
             NodeCreateResult::Err(e) => return NodeCreateResult::Err(e),
             NodeCreateResult::None => {},
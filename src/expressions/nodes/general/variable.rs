@@ -2,13 +2,15 @@ use crate::context::render_context::RenderContext;
 use crate::expressions::errors::evaluation_error::EvaluationError;
 use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult};
 use crate::common::variable::{Variable as CommonVariable};
+use crate::context::parsing_context::ParsingContext;
 
 //// A variable from context
 pub struct Variable {
     variable_name: String,
+    offset: usize,
 }
 
-pub fn try_create_from_string(expression: String, _offset: usize) -> NodeCreateResult {
+pub fn try_create_from_string(expression: String, offset: usize, _context: &ParsingContext) -> NodeCreateResult {
     let is_first_char_alphabetic = match expression.find(char::is_alphabetic) {
         Some(i) => i == 0,
         None => false
@@ -28,10 +30,10 @@ pub fn try_create_from_string(expression: String, _offset: usize) -> NodeCreateR
         Some(c) => '(' == c
     };
     if is_function_call {
-        return NodeCreateResult::None; // TODO: implement function calls
+        return NodeCreateResult::None; // handled by the function_call module
     }
 
-    let node = Variable::new(expression[..cursor+1].to_string());
+    let node = Variable::new_at(expression[..cursor+1].to_string(), offset);
     return NodeCreateResult::Some((Box::new(node), cursor+1));
 }
 
@@ -39,15 +41,28 @@ impl Variable {
     pub fn new(variable_name: String) -> Variable {
         Variable {
             variable_name: variable_name,
+            offset: 0,
+        }
+    }
+
+    /// A variable found at the given offset of a template, which is reported if it is undefined
+    pub fn new_at(variable_name: String, offset: usize) -> Variable {
+        Variable {
+            variable_name,
+            offset,
         }
     }
 }
 
 impl Node for Variable {
     fn evaluate(&self, context: &RenderContext) -> Result<CommonVariable, EvaluationError> {
-        match context.parameters.get(&self.variable_name) {
-            Some(p) => Ok(p.clone()),
-            None => Err(EvaluationError::new(format!("Variable not found: {}", self.variable_name)))
+        let variable = match context.get_variable(&self.variable_name) {
+            Some(p) => p.clone(),
+            None => return context.get_undefined(Some(self.offset), format!("Variable not found: {}", self.variable_name)),
+        };
+        match context.resolve(variable) {
+            Ok(v) => Ok(v),
+            Err(e) => Err(EvaluationError::new(format!("Variable \"{}\" failed: {}", self.variable_name, e.message))),
         }
     }
 
@@ -62,6 +77,10 @@ impl Node for Variable {
     fn get_type(&self) -> &str {
         "variable"
     }
+
+    fn get_path(&self) -> Option<String> {
+        Some(self.variable_name.clone())
+    }
 }
 
 #[cfg(test)]
@@ -70,15 +89,15 @@ mod tests {
 
     fn get_context() -> RenderContext {
         let mut context = RenderContext::new();
-        context.parameters.insert(String::from("user1"), CommonVariable::new_from_str("Alpha"));
-        context.parameters.insert(String::from("user2"), CommonVariable::new_from_str("Bravo"));
-        context.parameters.insert(String::from("user3"), CommonVariable::new_from_str("Charlie"));
+        context.set_parameter("user1", CommonVariable::new_from_str("Alpha"));
+        context.set_parameter("user2", CommonVariable::new_from_str("Bravo"));
+        context.set_parameter("user3", CommonVariable::new_from_str("Charlie"));
         context
     }
 
     #[test]
     fn test_expressions_nodes_general_variable_correct() {
-        let result = match try_create_from_string(String::from("user2 otherstuff"), 0) {
+        let result = match try_create_from_string(String::from("user2 otherstuff"), 0, &ParsingContext::new()) {
             NodeCreateResult::Some(result) => result,
             NodeCreateResult::Err(e) => panic!("Exprected a result, got an error: {}", e),
             NodeCreateResult::None => panic!("Exprected a result, got None"),
@@ -96,7 +115,7 @@ mod tests {
 
     #[test]
     fn test_expressions_nodes_general_variable_non_existing_var() {
-        let result = match try_create_from_string(String::from("user4 otherstuff"), 0) {
+        let result = match try_create_from_string(String::from("user4 otherstuff"), 0, &ParsingContext::new()) {
             NodeCreateResult::Some(result) => result,
             NodeCreateResult::Err(e) => panic!("Exprected a result, got an error: {}", e),
             NodeCreateResult::None => panic!("Exprected a result, got None"),
@@ -114,7 +133,7 @@ mod tests {
 
     #[test]
     fn test_expressions_nodes_general_variable_function() {
-        match try_create_from_string(String::from("my_function(arg1, arg2) abc"), 0) {
+        match try_create_from_string(String::from("my_function(arg1, arg2) abc"), 0, &ParsingContext::new()) {
             NodeCreateResult::Some(_) => panic!("Exprected None, but got a result"),
             NodeCreateResult::Err(e) => panic!("Exprected None, but got an error: {}", e),
             NodeCreateResult::None => {},
@@ -123,7 +142,7 @@ mod tests {
 
     #[test]
     fn test_expressions_nodes_general_variable_not_var_string_literal() {
-        match try_create_from_string(String::from("\"hello\" stuff"), 0) {
+        match try_create_from_string(String::from("\"hello\" stuff"), 0, &ParsingContext::new()) {
             NodeCreateResult::Some(_) => panic!("Exprected None, but got a result"),
             NodeCreateResult::Err(e) => panic!("Exprected None, but got an error: {}", e),
             NodeCreateResult::None => {},
@@ -132,10 +151,42 @@ mod tests {
 
     #[test]
     fn test_expressions_nodes_general_variable_not_var_int_literal() {
-        match try_create_from_string(String::from("1234 stuff"), 0) {
+        match try_create_from_string(String::from("1234 stuff"), 0, &ParsingContext::new()) {
             NodeCreateResult::Some(_) => panic!("Exprected None, but got a result"),
             NodeCreateResult::Err(e) => panic!("Exprected None, but got an error: {}", e),
             NodeCreateResult::None => {},
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_expressions_nodes_general_variable_lazy() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use crate::common::variable::VariableStore;
+        use crate::expressions;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let mut context = get_context();
+        context.set_parameter("total", CommonVariable::new_lazy(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(CommonVariable::new_from_int(42))
+        }));
+        let mut stats = VariableStore::new();
+        stats.insert(String::from("count"), CommonVariable::new_lazy(|| Ok(CommonVariable::new_from_int(7))));
+        context.set_parameter("stats", CommonVariable::new_from_map(stats));
+        context.set_parameter("broken", CommonVariable::new_lazy(|| Err(EvaluationError::new(String::from("No connection")))));
+
+        let evaluate = |expression: &str| match expressions::parse(String::from(expression), &ParsingContext::new()) {
+            Ok(node) => node.evaluate(&context),
+            Err(e) => panic!("Expected a node, got an error: {}", e),
+        };
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(evaluate("total + total").ok().unwrap().get_int_value(), Some(84));
+        assert_eq!(evaluate("total").ok().unwrap().get_int_value(), Some(42));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(evaluate("stats.count * 2").ok().unwrap().get_int_value(), Some(14));
+        assert_eq!(evaluate("stats['count']").ok().unwrap().get_int_value(), Some(7));
+        assert_eq!(evaluate("broken").err().unwrap().message, "Variable \"broken\" failed: No connection");
+    }
+}
@@ -1,16 +1,19 @@
 pub mod and;
+pub mod null_coalescing;
 pub mod or;
 
 use crate::expressions::nodes::{NodeCreator, NodeCreateResult};
+use crate::context::parsing_context::ParsingContext;
 
-const NODE_CREATORS: [NodeCreator; 2] = [
+const NODE_CREATORS: [NodeCreator; 3] = [
     and::try_create_from_string,
+    null_coalescing::try_create_from_string,
     or::try_create_from_string,
 ];
 
-pub fn try_create_from_string(string_remain: String, offset: usize) -> NodeCreateResult {
+pub fn try_create_from_string(string_remain: String, offset: usize, context: &ParsingContext) -> NodeCreateResult {
     for node_creator in &NODE_CREATORS {
-        match node_creator(string_remain.clone(), offset) {
+        match node_creator(string_remain.clone(), offset, context) {
             NodeCreateResult::Some(r) => return NodeCreateResult::Some(r),
             NodeCreateResult::Err(e) => return NodeCreateResult::Err(e),
             NodeCreateResult::None => {},
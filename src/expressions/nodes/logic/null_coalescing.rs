@@ -0,0 +1,99 @@
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::nodes::{Associativity, BinaryOperands, Node, NodeCreateResult};
+use crate::expressions::nodes::precedence;
+use crate::common::variable::Variable;
+use crate::context::parsing_context::ParsingContext;
+
+const SYMBOL_FORM: &str = "??";
+
+/// Null-coalescing operator: `user.nickname ?? user.name`. Returns the right operand
+/// if the left one is null or undefined, even if undefined values are errors
+pub struct NullCoalescing {
+    operands: BinaryOperands,
+}
+
+impl NullCoalescing {
+    fn new() -> NullCoalescing {
+        NullCoalescing {
+            operands: [None, None],
+        }
+    }
+}
+
+pub fn try_create_from_string(expression: String, _offset: usize, _context: &ParsingContext) -> NodeCreateResult {
+    match expression.starts_with(SYMBOL_FORM) {
+        true => NodeCreateResult::Some((Box::new(NullCoalescing::new()), SYMBOL_FORM.len())),
+        false => NodeCreateResult::None,
+    }
+}
+
+impl Node for NullCoalescing {
+    fn evaluate(&self, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        for (i, operand) in self.operands.iter().enumerate() {
+            match operand {
+                None => Err(EvaluationError::new(format!("Operand with index '{}' is not defined", i))),
+                Some(_) => Ok(()),
+            }?;
+        }
+        match self.operands[0].as_ref().unwrap().evaluate(context) {
+            Ok(Variable::Null) => {},
+            Err(e) if e.is_undefined => {},
+            result => return result,
+        }
+        self.operands[1].as_ref().unwrap().evaluate(context)
+    }
+
+    fn is_operator(&self) -> bool {
+        true
+    }
+
+    fn set_binary_operands(&mut self, operands: BinaryOperands) {
+        self.operands = operands;
+    }
+
+    fn get_type(&self) -> &str {
+        "null_coalescing"
+    }
+
+    fn get_precedence(&self) -> usize {
+        precedence::NULL_COALESCING
+    }
+
+    fn get_associativity(&self) -> Associativity {
+        Associativity::Right
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::render_context::UndefinedPolicy;
+    use crate::expressions;
+
+    fn evaluate(expression: &str, policy: UndefinedPolicy) -> Result<Variable, EvaluationError> {
+        let mut context = RenderContext::new();
+        context.undefined_policy = policy;
+        context.set_parameter("name", Variable::new_from_str("John"));
+        context.set_parameter("nothing", Variable::new_null());
+        context.set_parameter("items", Variable::from(vec![1, 2]));
+        match expressions::parse(String::from(expression), &ParsingContext::new()) {
+            Ok(node) => node.evaluate(&context),
+            Err(e) => panic!("Expected a node, got an error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_expressions_node_logic_null_coalescing() {
+        for policy in [UndefinedPolicy::Strict, UndefinedPolicy::Lenient, UndefinedPolicy::Chainable].iter() {
+            assert_eq!(evaluate("name ?? 'Guest'", *policy).ok().unwrap().get_string_value(), "John");
+            assert_eq!(evaluate("missing ?? 'Guest'", *policy).ok().unwrap().get_string_value(), "Guest");
+            assert_eq!(evaluate("nothing ?? missing ?? 'x' ~ 'y'", *policy).ok().unwrap().get_string_value(), "xy");
+            assert_eq!(evaluate("missing.name.first ?? items[5] ?? 3", *policy).ok().unwrap().get_int_value(), Some(3));
+            assert_eq!(evaluate("nothing['key'] ?? 0", *policy).ok().unwrap().get_int_value(), Some(0));
+        }
+        assert_eq!(evaluate("'' ?? 'x'", UndefinedPolicy::Strict).ok().unwrap().get_string_value(), "");
+        assert_eq!(evaluate("name.first ?? 'x'", UndefinedPolicy::Strict).err().unwrap().message,
+            "Cannot get attribute \"first\" of a value of type \"string\": name");
+    }
+}
@@ -1,7 +1,9 @@
 use crate::context::render_context::RenderContext;
 use crate::expressions::errors::evaluation_error::EvaluationError;
 use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult};
+use crate::expressions::nodes::precedence;
 use crate::common::variable::Variable;
+use crate::context::parsing_context::ParsingContext;
 
 const WORD_FORM: &str = "or";
 const SYMBOL_FORM: &str = "||";
@@ -19,7 +21,7 @@ impl Or {
     }
 }
 
-pub fn try_create_from_string(expression: String, _offset: usize) -> NodeCreateResult {
+pub fn try_create_from_string(expression: String, _offset: usize, _context: &ParsingContext) -> NodeCreateResult {
     let is_symbol_form = expression.starts_with(SYMBOL_FORM);
     let is_word_form = expression.starts_with(WORD_FORM) &&
         (expression.len() <= WORD_FORM.len() ||
@@ -61,6 +63,10 @@ impl Node for Or {
     fn get_type(&self) -> &str {
         "or"
     }
+
+    fn get_precedence(&self) -> usize {
+        precedence::OR
+    }
 }
 
 #[cfg(test)]
@@ -71,12 +77,12 @@ mod tests {
 
     #[test]
     fn test_expressions_node_logic_or_try_create_from_string_valid() {
-        match try_create_from_string(String::from("|| 2"), 0) {
+        match try_create_from_string(String::from("|| 2"), 0, &ParsingContext::new()) {
             NodeCreateResult::Some(_) => {},
             NodeCreateResult::None => panic!("Expected an operator, got None"),
             NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
         };
-        match try_create_from_string(String::from("or 2"), 0) {
+        match try_create_from_string(String::from("or 2"), 0, &ParsingContext::new()) {
             NodeCreateResult::Some(_) => {},
             NodeCreateResult::None => panic!("Expected an operator, got None"),
             NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
@@ -85,7 +91,7 @@ mod tests {
 
     #[test]
     fn test_expressions_node_logic_or_try_create_from_string_long_alphabetic() {
-        match try_create_from_string(String::from("ornotanoperator 2"), 0) {
+        match try_create_from_string(String::from("ornotanoperator 2"), 0, &ParsingContext::new()) {
             NodeCreateResult::Some(_) => panic!("Expected None, got Result"),
             NodeCreateResult::None => {},
             NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
@@ -1,28 +1,73 @@
 pub mod arithmetic;
+pub mod comparison;
 pub mod general;
 pub mod logic;
+pub mod postfix;
+pub mod precedence;
+pub mod string;
+pub mod unary;
 
+use crate::context::parsing_context::ParsingContext;
 use crate::context::render_context::RenderContext;
 use crate::expressions::errors::evaluation_error::EvaluationError;
 use crate::expressions::errors::parsing_error::ParsingError;
 use crate::common::variable::Variable;
 
 type BinaryOperands = [Option<Box<dyn Node>>; 2];
+type UnaryOperand = Option<Box<dyn Node>>;
 
-pub const NODE_CREATORS: [NodeCreator; 3] = [
+pub const NODE_CREATORS: [NodeCreator; 5] = [
     // IMPORTANT: item order affects on node detector priority.
     // Keep the GENERAL module last
     logic::try_create_from_string,
+    comparison::try_create_from_string,
     arithmetic::try_create_from_string,
+    string::try_create_from_string,
 
     general::try_create_from_string,
 ];
 
-pub trait Node {
+/// Defines how a sequence of operators with the same precedence is grouped
+pub enum Associativity {
+    /// `a - b - c` is evaluated as `(a - b) - c`
+    Left,
+    /// `a ** b ** c` is evaluated as `a ** (b ** c)`
+    Right,
+}
+
+pub trait Node: Send + Sync {
     fn evaluate(&self, context: &RenderContext) -> Result<Variable, EvaluationError>;
     fn is_operator(&self) -> bool;
     fn set_binary_operands(&mut self, operands: BinaryOperands);
     fn get_type(&self) -> &str;
+
+    /// Binding power of an operator. See the `precedence` module for values
+    fn get_precedence(&self) -> usize {
+        0
+    }
+
+    fn get_associativity(&self) -> Associativity {
+        Associativity::Left
+    }
+
+    /// True for prefix operators like `not` or `-` which take a single operand
+    fn is_unary_operator(&self) -> bool {
+        false
+    }
+
+    /// Dotted path of a variable or an attribute, e.g. `user.address`. Used in error messages
+    fn get_path(&self) -> Option<String> {
+        None
+    }
+
+    /// True for filters (`name|upper`) and attributes (`user.name`) which apply to the preceding operand
+    fn is_postfix_operator(&self) -> bool {
+        false
+    }
+
+    fn set_unary_operand(&mut self, _operand: UnaryOperand) {
+
+    }
 }
 
 pub enum NodeCreateResult {
@@ -31,4 +76,4 @@ pub enum NodeCreateResult {
     Err(ParsingError),
 }
 
-pub type NodeCreator = fn(expression: String, offset: usize) -> NodeCreateResult;
\ No newline at end of file
+pub type NodeCreator = fn(expression: String, offset: usize, context: &ParsingContext) -> NodeCreateResult;
\ No newline at end of file
@@ -0,0 +1,258 @@
+use crate::context::parsing_context::ParsingContext;
+use crate::context::render_context::{RenderContext, UndefinedPolicy};
+use crate::expressions;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::errors::parsing_error::ParsingError;
+use crate::expressions::functions::NamedArgs;
+use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult, UnaryOperand};
+use crate::expressions::nodes::general::arguments::Arguments;
+use crate::common::template_object::TemplateObject;
+use crate::common::variable::Variable;
+
+/// Access to an attribute of the preceding map or object, e.g. `.city` in `user.address.city`,
+/// or a method call of the preceding object, e.g. `.total("EUR")` in `order.total("EUR")`
+pub struct Attribute {
+    name: String,
+    arguments: Option<Arguments>,
+    operand: UnaryOperand,
+    // offset of the name in the expression
+    offset: usize,
+}
+
+impl Attribute {
+    pub fn new(name: String) -> Attribute {
+        Attribute {
+            name,
+            arguments: None,
+            operand: None,
+            offset: 0,
+        }
+    }
+
+    pub fn new_method_call(name: String, arguments: Arguments) -> Attribute {
+        Attribute {
+            name,
+            arguments: Some(arguments),
+            operand: None,
+            offset: 0,
+        }
+    }
+
+    fn get_operand_path(&self) -> Option<String> {
+        self.operand.as_ref().and_then(|o| o.get_path())
+    }
+
+    // An attribute of an object, or a method without arguments if there is no such attribute
+    fn evaluate_object(&self, object: &dyn TemplateObject, path: &str, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        let result = match &self.arguments {
+            None => match object.get_attribute(&self.name) {
+                Some(v) => return context.resolve(v),
+                None => object.call_method(&self.name, &[], &NamedArgs::new()),
+            },
+            Some(arguments) => {
+                let (args, named_args) = arguments.evaluate(context)?;
+                object.call_method(&self.name, &args, &named_args)
+            },
+        };
+        match result {
+            Some(Ok(v)) => context.resolve(v),
+            Some(Err(e)) => Err(EvaluationError::new(format!("Method \"{}\" failed: {}", self.name, e.message))),
+            None if self.arguments.is_some() => Err(EvaluationError::new(format!("Method not found: {}", path))),
+            None => context.get_undefined(Some(self.offset), format!("Attribute not found: {}", path)),
+        }
+    }
+}
+
+pub fn try_create_from_string(expression: String, offset: usize, context: &ParsingContext) -> NodeCreateResult {
+    if !expression.starts_with('.') {
+        return NodeCreateResult::None;
+    }
+    let name_end = match expression[1..].find(|c: char| !(c.is_alphanumeric() || '_' == c)) {
+        Some(i) => i + 1,
+        None => expression.len(),
+    };
+    if name_end == 1 {
+        return NodeCreateResult::Err(ParsingError::new(offset + 1, String::from("Expected an attribute name after \".\"")));
+    }
+    let name = expression[1..name_end].to_string();
+    if !expression[name_end..].starts_with('(') {
+        let mut node = Attribute::new(name);
+        node.offset = offset + 1;
+        return NodeCreateResult::Some((Box::new(node), name_end));
+    }
+
+    let end_pos = match expressions::find_closing_bracket(&expression[name_end..], offset + name_end) {
+        Ok(p) => name_end + p,
+        Err(e) => return NodeCreateResult::Err(e),
+    };
+    let arguments = match Arguments::parse(&expression[name_end+1..end_pos], offset + name_end + 1, context) {
+        Ok(a) => a,
+        Err(e) => return NodeCreateResult::Err(e),
+    };
+    let mut node = Attribute::new_method_call(name, arguments);
+    node.offset = offset + 1;
+    NodeCreateResult::Some((Box::new(node), end_pos + 1))
+}
+
+impl Node for Attribute {
+    fn evaluate(&self, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        let object = match &self.operand {
+            Some(o) => o.evaluate(context)?,
+            None => return Err(EvaluationError::new(format!("Attribute \"{}\" is not applied to a value", self.name))),
+        };
+        let path = match self.get_path() {
+            Some(p) => p,
+            None => self.name.clone() + if self.arguments.is_some() { "()" } else { "" },
+        };
+        if let Some(o) = object.get_object_value() {
+            return self.evaluate_object(o.as_ref(), &path, context);
+        }
+        if object.is_null() && context.undefined_policy == UndefinedPolicy::Chainable {
+            return Ok(Variable::Null);
+        }
+        let action = if self.arguments.is_some() { "call method" } else { "get attribute" };
+        let store = match (object.get_struct_value(), &self.arguments) {
+            (Some(s), None) => s,
+            _ => {
+                let message = format!("Cannot {} \"{}\" of a value of type \"{}\": {}",
+                    action, self.name, object.get_type_name(), self.get_operand_path().unwrap_or_else(|| object.get_string_value()));
+                return Err(if object.is_null() {
+                    EvaluationError::new_undefined(Some(self.offset), message)
+                } else {
+                    EvaluationError::new(message)
+                });
+            },
+        };
+        match store.get(&self.name) {
+            Some(v) => context.resolve(v.clone()),
+            None => context.get_undefined(Some(self.offset), format!("Attribute not found: {}", path)),
+        }
+    }
+
+    fn is_operator(&self) -> bool {
+        false
+    }
+
+    fn set_binary_operands(&mut self, _operands: BinaryOperands) {
+
+    }
+
+    fn get_type(&self) -> &str {
+        "attribute"
+    }
+
+    fn is_postfix_operator(&self) -> bool {
+        true
+    }
+
+    fn set_unary_operand(&mut self, operand: UnaryOperand) {
+        self.operand = operand;
+    }
+
+    fn get_path(&self) -> Option<String> {
+        let call = if self.arguments.is_some() { "()" } else { "" };
+        self.get_operand_path().map(|p| p + "." + self.name.as_str() + call)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::variable::VariableStore;
+    use crate::expressions;
+
+    struct Order {
+        prices: Vec<i128>,
+    }
+
+    impl TemplateObject for Order {
+        fn get_attribute(&self, name: &str) -> Option<Variable> {
+            match name {
+                "count" => Some(Variable::from(self.prices.len())),
+                _ => None,
+            }
+        }
+
+        fn call_method(&self, name: &str, args: &[Variable], named_args: &NamedArgs) -> Option<Result<Variable, EvaluationError>> {
+            match name {
+                "total" => {
+                    let discount = args.first().or_else(|| named_args.get("discount")).and_then(|d| d.get_int_value()).unwrap_or(0);
+                    if discount < 0 {
+                        return Some(Err(EvaluationError::new(String::from("Discount must be positive"))));
+                    }
+                    Some(Ok(Variable::from(self.prices.iter().sum::<i128>() - discount)))
+                },
+                "first" => Some(Ok(Variable::new_from_object(Order { prices: self.prices[..1].to_vec() }))),
+                _ => None,
+            }
+        }
+    }
+
+    fn get_context() -> RenderContext {
+        let mut address = VariableStore::new();
+        address.insert(String::from("city"), Variable::new_from_str("Paris"));
+        let mut user = VariableStore::new();
+        user.insert(String::from("address"), Variable::new_from_struct(address));
+        user.insert(String::from("age"), Variable::new_from_int(42));
+        let mut context = RenderContext::new();
+        context.set_parameter("user", Variable::new_from_struct(user));
+        context.set_parameter("order", Variable::new_from_object(Order { prices: vec![10, 20] }));
+        context
+    }
+
+    fn evaluate(expression: &str) -> Result<Variable, EvaluationError> {
+        match expressions::parse(String::from(expression), &ParsingContext::new()) {
+            Ok(node) => node.evaluate(&get_context()),
+            Err(e) => panic!("Expected a node, got an error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_expressions_nodes_postfix_attribute_try_create_from_string() {
+        match try_create_from_string(String::from(".city_name + 1"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some((_, cursor)) => assert_eq!(cursor, 10),
+            NodeCreateResult::Err(e) => panic!("Exprected a result, got an error: {}", e),
+            NodeCreateResult::None => panic!("Exprected a result, got None"),
+        };
+        match try_create_from_string(String::from(". city"), 10, &ParsingContext::new()) {
+            NodeCreateResult::Err(e) => assert_eq!(e.offset, 11),
+            _ => panic!("Exprected an error"),
+        };
+    }
+
+    #[test]
+    fn test_expressions_nodes_postfix_attribute_nested() {
+        assert_eq!(evaluate("user.address.city").ok().unwrap().get_string_value(), "Paris");
+        assert_eq!(evaluate("user.age + 1").ok().unwrap().get_int_value(), Some(43));
+        assert_eq!(evaluate("(user.address).city ~ '!'").ok().unwrap().get_string_value(), "Paris!");
+    }
+
+    #[test]
+    fn test_expressions_nodes_postfix_attribute_errors() {
+        assert_eq!(evaluate("user.address.zip").err().unwrap().message, "Attribute not found: user.address.zip");
+        assert_eq!(evaluate("(user).name").err().unwrap().message, "Attribute not found: name");
+        assert_eq!(evaluate("user.age.value").err().unwrap().message,
+            "Cannot get attribute \"value\" of a value of type \"int\": user.age");
+        assert_eq!(evaluate("user.phone.number").err().unwrap().message, "Attribute not found: user.phone");
+    }
+
+    #[test]
+    fn test_expressions_nodes_postfix_attribute_object() {
+        assert_eq!(evaluate("order.count").ok().unwrap().get_int_value(), Some(2));
+        assert_eq!(evaluate("order.total").ok().unwrap().get_int_value(), Some(30));
+        assert_eq!(evaluate("order.total(5) + 1").ok().unwrap().get_int_value(), Some(26));
+        assert_eq!(evaluate("order.total(discount=order.count)").ok().unwrap().get_int_value(), Some(28));
+        assert_eq!(evaluate("order.first().total()").ok().unwrap().get_int_value(), Some(10));
+        assert_eq!(evaluate("order['count']").ok().unwrap().get_int_value(), Some(2));
+        assert_eq!(evaluate("order ~ ''").ok().unwrap().get_string_value(), "[object]");
+    }
+
+    #[test]
+    fn test_expressions_nodes_postfix_attribute_object_errors() {
+        assert_eq!(evaluate("order.first().id").err().unwrap().message, "Attribute not found: order.first().id");
+        assert_eq!(evaluate("order.count()").err().unwrap().message, "Method not found: order.count()");
+        assert_eq!(evaluate("order.total(-1)").err().unwrap().message, "Method \"total\" failed: Discount must be positive");
+        assert_eq!(evaluate("user.address.city()").err().unwrap().message,
+            "Cannot call method \"city\" of a value of type \"map\": user.address");
+    }
+}
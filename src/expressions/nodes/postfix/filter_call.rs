@@ -0,0 +1,157 @@
+use crate::context::parsing_context::ParsingContext;
+use crate::context::render_context::RenderContext;
+use crate::expressions;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::errors::parsing_error::ParsingError;
+use crate::expressions::filters::Filter;
+use crate::expressions::filters::general;
+use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult, UnaryOperand};
+use crate::expressions::nodes::general::arguments::Arguments;
+use crate::common::variable::Variable;
+
+/// Application of a registered filter to the preceding operand, e.g. `name|upper` or `title|trim("-")`
+pub struct FilterCall {
+    name: String,
+    filter: Filter,
+    arguments: Arguments,
+    operand: UnaryOperand,
+}
+
+impl FilterCall {
+    pub fn new(name: String, filter: Filter, arguments: Arguments) -> FilterCall {
+        FilterCall {
+            name,
+            filter,
+            arguments,
+            operand: None,
+        }
+    }
+}
+
+pub fn try_create_from_string(expression: String, offset: usize, context: &ParsingContext) -> NodeCreateResult {
+    // "||" is the logical OR
+    if !expression.starts_with('|') || expression.starts_with("||") {
+        return NodeCreateResult::None;
+    }
+    let name_start = expression.len() - expression[1..].trim_start().len();
+    let name_end = match expression[name_start..].find(|c: char| !(c.is_alphanumeric() || '_' == c)) {
+        Some(i) => name_start + i,
+        None => expression.len(),
+    };
+    let name = &expression[name_start..name_end];
+    if !name.starts_with(char::is_alphabetic) {
+        return NodeCreateResult::Err(ParsingError::new(offset + name_start, String::from("Expected a filter name after \"|\"")));
+    }
+    let filter = match context.filters.get(name) {
+        Some(f) => *f,
+        None => return NodeCreateResult::Err(ParsingError::new(offset + name_start, format!("Unknown filter \"{}\"", name))),
+    };
+    if !expression[name_end..].starts_with('(') {
+        return NodeCreateResult::Some((Box::new(FilterCall::new(String::from(name), filter, Arguments::new())), name_end));
+    }
+
+    let end_pos = match expressions::find_closing_bracket(&expression[name_end..], offset + name_end) {
+        Ok(p) => name_end + p,
+        Err(e) => return NodeCreateResult::Err(e),
+    };
+    let arguments = match Arguments::parse(&expression[name_end+1..end_pos], offset + name_end + 1, context) {
+        Ok(a) => a,
+        Err(e) => return NodeCreateResult::Err(e),
+    };
+    NodeCreateResult::Some((Box::new(FilterCall::new(String::from(name), filter, arguments)), end_pos + 1))
+}
+
+impl Node for FilterCall {
+    fn evaluate(&self, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        let value = match &self.operand {
+            Some(o) => o.evaluate(context),
+            None => return Err(EvaluationError::new(format!("Filter \"{}\" is not applied to a value", self.name))),
+        };
+        // `missing|default("x")` works even if undefined values are errors
+        let value = match value {
+            Err(e) if e.is_undefined && self.name == general::DEFAULT_FILTER => Variable::Null,
+            v => context.resolve_nested(v?)?,
+        };
+        let (args, named_args) = self.arguments.evaluate(context)?;
+        match (self.filter)(&value, &args, &named_args, context) {
+            Ok(v) => Ok(v),
+            Err(e) => Err(EvaluationError::new(format!("Filter \"{}\" failed: {}", self.name, e.message))),
+        }
+    }
+
+    fn is_operator(&self) -> bool {
+        false
+    }
+
+    fn set_binary_operands(&mut self, _operands: BinaryOperands) {
+
+    }
+
+    fn get_type(&self) -> &str {
+        "filter"
+    }
+
+    fn is_postfix_operator(&self) -> bool {
+        true
+    }
+
+    fn set_unary_operand(&mut self, operand: UnaryOperand) {
+        self.operand = operand;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::filters;
+    use crate::expressions::nodes::general::literal::Literal;
+
+    fn apply(expression: &str, value: &str) -> (String, usize) {
+        let mut context = ParsingContext::new();
+        context.filters = filters::get_builtin_filters();
+        let (mut node, cursor) = match try_create_from_string(String::from(expression), 0, &context) {
+            NodeCreateResult::Some(result) => result,
+            NodeCreateResult::Err(e) => panic!("Exprected a result, got an error: {}", e),
+            NodeCreateResult::None => panic!("Exprected a result, got None"),
+        };
+        node.set_unary_operand(Some(Box::new(Literal::new_from_str(value))));
+        match node.evaluate(&RenderContext::new()) {
+            Ok(p) => (p.get_string_value(), cursor),
+            Err(e) => panic!("Expected a parameter, got an error: {}", e),
+        }
+    }
+
+    fn apply_error(expression: &str) -> ParsingError {
+        let mut context = ParsingContext::new();
+        context.filters = filters::get_builtin_filters();
+        match try_create_from_string(String::from(expression), 10, &context) {
+            NodeCreateResult::Some(_) => panic!("Exprected an error, but got a result"),
+            NodeCreateResult::Err(e) => e,
+            NodeCreateResult::None => panic!("Exprected an error, got None"),
+        }
+    }
+
+    #[test]
+    fn test_expressions_nodes_postfix_filter_call() {
+        assert_eq!(apply("|upper ~ 'x'", "Hello"), (String::from("HELLO"), 6));
+        assert_eq!(apply("| trim('-')|upper", "-hi-"), (String::from("hi"), 11));
+    }
+
+    #[test]
+    fn test_expressions_nodes_postfix_filter_call_not_a_filter() {
+        match try_create_from_string(String::from("|| true"), 0, &ParsingContext::new()) {
+            NodeCreateResult::None => {},
+            _ => panic!("Exprected None"),
+        }
+    }
+
+    #[test]
+    fn test_expressions_nodes_postfix_filter_call_errors() {
+        let err = apply_error("|unknown");
+        assert_eq!((err.offset, err.message.as_str()), (11, "Unknown filter \"unknown\""));
+        let err = apply_error("| 2");
+        assert_eq!((err.offset, err.message.as_str()), (12, "Expected a filter name after \"|\""));
+        let err = apply_error("|trim('-'");
+        assert_eq!((err.offset, err.message.as_str()), (15, "Bracket is not closed"));
+    }
+}
@@ -0,0 +1,23 @@
+pub mod attribute;
+pub mod filter_call;
+pub mod subscript;
+
+use crate::expressions::nodes::{NodeCreator, NodeCreateResult};
+use crate::context::parsing_context::ParsingContext;
+
+const NODE_CREATORS: [NodeCreator; 3] = [
+    attribute::try_create_from_string,
+    filter_call::try_create_from_string,
+    subscript::try_create_from_string,
+];
+
+pub fn try_create_from_string(string_remain: String, offset: usize, context: &ParsingContext) -> NodeCreateResult {
+    for node_creator in &NODE_CREATORS {
+        match node_creator(string_remain.clone(), offset, context) {
+            NodeCreateResult::Some(r) => return NodeCreateResult::Some(r),
+            NodeCreateResult::Err(e) => return NodeCreateResult::Err(e),
+            NodeCreateResult::None => {},
+        }
+    }
+    NodeCreateResult::None
+}
@@ -0,0 +1,286 @@
+use crate::context::parsing_context::ParsingContext;
+use crate::context::render_context::{RenderContext, UndefinedPolicy};
+use crate::expressions;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::errors::parsing_error::ParsingError;
+use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult, UnaryOperand};
+use crate::common::variable::Variable;
+
+enum SubscriptKind {
+    /// `items[0]`, `map["key"]`
+    Index(Box<dyn Node>),
+    /// `items[start:length]`, both parts are optional
+    Slice(Option<Box<dyn Node>>, Option<Box<dyn Node>>),
+}
+
+/// Subscript of the preceding list, string, map or object: `items[-1]`, `row[column.name]`, `title[0:10]`.
+/// Strings are indexed by characters. A slice takes a start and a length, like in Twig
+pub struct Subscript {
+    kind: SubscriptKind,
+    operand: UnaryOperand,
+    // offset of the opening bracket in the expression
+    offset: usize,
+}
+
+impl Subscript {
+    fn new(kind: SubscriptKind, offset: usize) -> Subscript {
+        Subscript {
+            kind,
+            operand: None,
+            offset,
+        }
+    }
+
+    fn get_operand_path(&self) -> Option<String> {
+        self.operand.as_ref().and_then(|o| o.get_path())
+    }
+
+    fn get_item(&self, object: &Variable, index: &Variable, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        if object.is_null() {
+            return match context.undefined_policy {
+                UndefinedPolicy::Chainable => Ok(Variable::Null),
+                _ => Err(EvaluationError::new_undefined(Some(self.offset), String::from("Cannot get an item of a value of type \"null\""))),
+            };
+        }
+        let key = index.get_string_value();
+        let attribute = match object {
+            Variable::Map(store) => Some(store.get(&key).cloned()),
+            Variable::Object(o) => Some(o.get_attribute(&key)),
+            _ => None,
+        };
+        if let Some(attribute) = attribute {
+            return match attribute {
+                Some(v) => Ok(v),
+                None => context.get_undefined(Some(self.offset), match self.get_operand_path() {
+                    Some(path) => format!("Key \"{}\" not found in {}", key, path),
+                    None => format!("Key \"{}\" not found", key),
+                }),
+            };
+        }
+        let index = match index.get_int_value() {
+            Some(i) => i,
+            None => return Err(EvaluationError::new(format!("Index must be an integer, got {}: {}",
+                index.get_type_name(), index.get_string_value()))),
+        };
+        if let Some(list) = object.get_list_value() {
+            return match resolve_index(index, list.len()) {
+                Some(i) => Ok(list[i].clone()),
+                None => context.get_undefined(Some(self.offset), format!("Index {} is out of range for a list of length {}", index, list.len())),
+            };
+        }
+        match object.get_type_name() {
+            "string" => {
+                let string = object.get_string_value();
+                let length = string.chars().count();
+                match resolve_index(index, length) {
+                    Some(i) => Ok(Variable::new_from_string(string.chars().nth(i).unwrap().to_string())),
+                    None => context.get_undefined(Some(self.offset), format!("Index {} is out of range for a string of length {}", index, length)),
+                }
+            },
+            type_name => Err(EvaluationError::new(format!("Cannot get an item of a value of type \"{}\"", type_name))),
+        }
+    }
+
+    fn get_slice(&self, object: &Variable, start: Option<Variable>, length: Option<Variable>) -> Result<Variable, EvaluationError> {
+        let start = get_slice_bound(start)?.unwrap_or(0);
+        let length = get_slice_bound(length)?;
+        if let Some(list) = object.get_list_value() {
+            let (from, to) = resolve_slice(start, length, list.len());
+            return Ok(Variable::new_from_list(list[from..to].to_vec()));
+        }
+        match object.get_type_name() {
+            "string" => {
+                let string = object.get_string_value();
+                let (from, to) = resolve_slice(start, length, string.chars().count());
+                Ok(Variable::new_from_string(string.chars().skip(from).take(to - from).collect()))
+            },
+            type_name => Err(EvaluationError::new(format!("Cannot slice a value of type \"{}\"", type_name))),
+        }
+    }
+}
+
+pub fn try_create_from_string(expression: String, offset: usize, context: &ParsingContext) -> NodeCreateResult {
+    if !expression.starts_with('[') {
+        return NodeCreateResult::None;
+    }
+    match parse_subscript(&expression, offset, context) {
+        Ok((kind, end_pos)) => NodeCreateResult::Some((Box::new(Subscript::new(kind, offset)), end_pos + 1)),
+        Err(e) => NodeCreateResult::Err(e),
+    }
+}
+
+// Returns the subscript and a position of the closing bracket
+fn parse_subscript(expression: &str, offset: usize, context: &ParsingContext) -> Result<(SubscriptKind, usize), ParsingError> {
+    let end_pos = expressions::find_closing_bracket(expression, offset)?;
+    let contents = &expression[1..end_pos];
+    let kind = match expressions::find_top_level_char(contents, b':', offset + 1)? {
+        Some(colon_pos) => SubscriptKind::Slice(
+            parse_optional(&contents[..colon_pos], offset + 1, context)?,
+            parse_optional(&contents[colon_pos+1..], offset + colon_pos + 2, context)?,
+        ),
+        None => match parse_optional(contents, offset + 1, context)? {
+            Some(index) => SubscriptKind::Index(index),
+            None => return Err(ParsingError::new(offset, String::from("Empty subscript"))),
+        },
+    };
+    Ok((kind, end_pos))
+}
+
+fn parse_optional(expression: &str, offset: usize, context: &ParsingContext) -> Result<Option<Box<dyn Node>>, ParsingError> {
+    if expression.trim().is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(expressions::parse_sub_expression(expression, offset, context)?))
+}
+
+fn get_slice_bound(bound: Option<Variable>) -> Result<Option<i128>, EvaluationError> {
+    match bound {
+        None => Ok(None),
+        Some(b) => match b.get_int_value() {
+            Some(i) => Ok(Some(i)),
+            None => Err(EvaluationError::new(format!("Slice bounds must be integers, got {}: {}",
+                b.get_type_name(), b.get_string_value()))),
+        },
+    }
+}
+
+// A negative index counts from the end
+fn resolve_index(index: i128, length: usize) -> Option<usize> {
+    let index = if index < 0 { index + length as i128 } else { index };
+    if index >= 0 && index < length as i128 {
+        Some(index as usize)
+    } else {
+        None
+    }
+}
+
+// Returns a range of a slice. A negative start counts from the end,
+// a negative length leaves that many items off the end
+fn resolve_slice(start: i128, length: Option<i128>, total: usize) -> (usize, usize) {
+    let total = total as i128;
+    let from = if start < 0 { total.saturating_add(start).max(0) } else { start.min(total) };
+    let to = match length {
+        None => total,
+        Some(l) if l < 0 => total.saturating_add(l).max(from),
+        Some(l) => from.saturating_add(l).min(total),
+    };
+    (from as usize, to as usize)
+}
+
+impl Node for Subscript {
+    fn evaluate(&self, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        let object = match &self.operand {
+            Some(o) => o.evaluate(context)?,
+            None => return Err(EvaluationError::new(String::from("Subscript is not applied to a value"))),
+        };
+        match &self.kind {
+            SubscriptKind::Index(index) => context.resolve(self.get_item(&object, &index.evaluate(context)?, context)?),
+            SubscriptKind::Slice(start, length) => {
+                let start = match start {
+                    Some(s) => Some(s.evaluate(context)?),
+                    None => None,
+                };
+                let length = match length {
+                    Some(l) => Some(l.evaluate(context)?),
+                    None => None,
+                };
+                self.get_slice(&object, start, length)
+            },
+        }
+    }
+
+    fn is_operator(&self) -> bool {
+        false
+    }
+
+    fn set_binary_operands(&mut self, _operands: BinaryOperands) {
+
+    }
+
+    fn get_type(&self) -> &str {
+        "subscript"
+    }
+
+    fn is_postfix_operator(&self) -> bool {
+        true
+    }
+
+    fn set_unary_operand(&mut self, operand: UnaryOperand) {
+        self.operand = operand;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::variable::VariableStore;
+
+    fn get_context() -> RenderContext {
+        let mut row = VariableStore::new();
+        row.insert(String::from("id"), Variable::new_from_int(7));
+        row.insert(String::from("title"), Variable::new_from_str("Hello"));
+        let mut column = VariableStore::new();
+        column.insert(String::from("name"), Variable::new_from_str("title"));
+        let items = (1..=5).map(Variable::new_from_int).collect();
+        let mut context = RenderContext::new();
+        context.set_parameter("row", Variable::new_from_struct(row));
+        context.set_parameter("column", Variable::new_from_struct(column));
+        context.set_parameter("items", Variable::new_from_list(items));
+        context.set_parameter("word", Variable::new_from_str("héllo"));
+        context
+    }
+
+    fn evaluate(expression: &str) -> Result<Variable, EvaluationError> {
+        match expressions::parse(String::from(expression), &ParsingContext::new()) {
+            Ok(node) => node.evaluate(&get_context()),
+            Err(e) => panic!("Expected a node, got an error: {}", e),
+        }
+    }
+
+    fn evaluate_to_string(expression: &str) -> String {
+        match evaluate(expression) {
+            Ok(v) => v.get_string_value(),
+            Err(e) => panic!("Expected a value, got an error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_expressions_nodes_postfix_subscript_index() {
+        assert_eq!(evaluate_to_string("items[0]"), "1");
+        assert_eq!(evaluate_to_string("items[-1] + items[1 + 1]"), "8");
+        assert_eq!(evaluate_to_string("word[1] ~ word[-1]"), "éo");
+        assert_eq!(evaluate_to_string("row['id']"), "7");
+        assert_eq!(evaluate_to_string("row[column.name][0]"), "H");
+    }
+
+    #[test]
+    fn test_expressions_nodes_postfix_subscript_slice() {
+        assert_eq!(evaluate_to_string("items[1:3]"), "[2, 3, 4]");
+        assert_eq!(evaluate_to_string("items[:2]"), "[1, 2]");
+        assert_eq!(evaluate_to_string("items[3:]"), "[4, 5]");
+        assert_eq!(evaluate_to_string("items[-2:]"), "[4, 5]");
+        assert_eq!(evaluate_to_string("items[1:-1]"), "[2, 3, 4]");
+        assert_eq!(evaluate_to_string("items[10:2]"), "[]");
+        assert_eq!(evaluate_to_string("word[1:3]"), "éll");
+        assert_eq!(evaluate_to_string("items[1:170141183460469231731687303715884105727]"), "[2, 3, 4, 5]");
+        assert_eq!(evaluate_to_string("items[-170141183460469231731687303715884105728:-170141183460469231731687303715884105728]"), "[]");
+    }
+
+    #[test]
+    fn test_expressions_nodes_postfix_subscript_errors() {
+        assert_eq!(evaluate("items[5]").err().unwrap().message, "Index 5 is out of range for a list of length 5");
+        assert_eq!(evaluate("items['a']").err().unwrap().message, "Index must be an integer, got string: a");
+        assert_eq!(evaluate("row['name']").err().unwrap().message, "Key \"name\" not found in row");
+        assert_eq!(evaluate("row['id'][0]").err().unwrap().message, "Cannot get an item of a value of type \"int\"");
+        assert_eq!(evaluate("row[:1]").err().unwrap().message, "Cannot slice a value of type \"map\"");
+
+        match try_create_from_string(String::from("[ ] + 1"), 10, &ParsingContext::new()) {
+            NodeCreateResult::Err(e) => assert_eq!((e.offset, e.message.as_str()), (10, "Empty subscript")),
+            _ => panic!("Exprected an error"),
+        };
+        match try_create_from_string(String::from("[1:2 +]"), 10, &ParsingContext::new()) {
+            NodeCreateResult::Err(e) => assert_eq!(e.offset, 16),
+            _ => panic!("Exprected an error"),
+        };
+    }
+}
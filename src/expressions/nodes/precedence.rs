@@ -0,0 +1,13 @@
+//! Binding power of operators. The higher the value, the stronger an operator binds its operands.
+//! Values follow the Twig precedence table, so that ported templates are grouped the same way.
+
+pub const NULL_COALESCING: usize = 5;
+pub const OR: usize = 10;
+pub const AND: usize = 15;
+pub const COMPARISON: usize = 20;
+pub const CONCATENATION: usize = 27;
+pub const ADDITIVE: usize = 30;
+pub const NOT: usize = 50;
+pub const MULTIPLICATIVE: usize = 60;
+pub const POWER: usize = 200;
+pub const UNARY_ARITHMETIC: usize = 500;
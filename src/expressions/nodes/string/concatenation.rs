@@ -0,0 +1,102 @@
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult};
+use crate::expressions::nodes::precedence;
+use crate::common::variable::Variable;
+use crate::context::parsing_context::ParsingContext;
+
+/// String concatenation. Both operands are converted to strings
+pub struct Concatenation {
+    operands: BinaryOperands,
+}
+
+impl Concatenation {
+    pub fn new() -> Concatenation {
+        Concatenation{
+            operands: [None, None],
+        }
+    }
+}
+
+impl Default for Concatenation {
+    fn default() -> Concatenation {
+        Concatenation::new()
+    }
+}
+
+pub fn try_create_from_string(expression: String, _offset: usize, _context: &ParsingContext) -> NodeCreateResult {
+    match expression.starts_with('~') {
+        true => NodeCreateResult::Some((Box::new(Concatenation::new()), 1)),
+        false => NodeCreateResult::None,
+    }
+}
+
+impl Node for Concatenation {
+    fn evaluate(&self, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        for (i, operand) in self.operands.iter().enumerate() {
+            match operand {
+                None => Err(EvaluationError::new(format!("Operand with index '{}' is not defined", i))),
+                Some(_) => Ok(()),
+            }?;
+        }
+        let operand1 = context.resolve_nested(self.operands[0].as_ref().unwrap().evaluate(context)?)?;
+        let operand2 = context.resolve_nested(self.operands[1].as_ref().unwrap().evaluate(context)?)?;
+
+        Ok(Variable::new_from_string(operand1.get_string_value() + operand2.get_string_value().as_str()))
+    }
+
+    fn is_operator(&self) -> bool {
+        true
+    }
+
+    fn set_binary_operands(&mut self, operands: BinaryOperands) {
+        self.operands = operands;
+    }
+
+    fn get_type(&self) -> &str {
+        "concatenation"
+    }
+
+    fn get_precedence(&self) -> usize {
+        precedence::CONCATENATION
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::render_context::RenderContext;
+    use crate::expressions::nodes::general::literal::Literal;
+
+    #[test]
+    fn test_expressions_node_string_concatenation_try_create_from_string_valid() {
+        match try_create_from_string(String::from("~ name"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some((_, cursor)) => assert_eq!(cursor, 1),
+            NodeCreateResult::None => panic!("Expected an operator, got None"),
+            NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_expressions_node_string_concatenation_try_create_from_string_none() {
+        match try_create_from_string(String::from("+ name"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some(_) => panic!("Expected None, got Result"),
+            NodeCreateResult::None => {},
+            NodeCreateResult::Err(e) => panic!("Expected None, got an error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_expressions_node_string_concatenation_mixed_types() {
+        let mut operator = Concatenation::new();
+        operator.set_binary_operands([
+            Some(Box::from(Literal::new_from_str("Total: "))),
+            Some(Box::from(Literal::new_from_int(42))),
+        ]);
+        let param = match operator.evaluate(&RenderContext::new()) {
+            Ok(p) => p,
+            Err(e) => panic!("Expected a string-type parameter, got an error: {}", e),
+        };
+        assert_eq!(param.get_string_value(), "Total: 42");
+    }
+}
@@ -0,0 +1,124 @@
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult, UnaryOperand};
+use crate::expressions::nodes::{arithmetic, precedence};
+use crate::expressions::nodes::general::literal;
+use crate::common::variable::Variable;
+use crate::context::parsing_context::ParsingContext;
+
+/// Numeric negation, e.g. `-amount`
+pub struct Negation {
+    operand: UnaryOperand,
+}
+
+impl Negation {
+    fn new() -> Negation {
+        Negation{
+            operand: None,
+        }
+    }
+}
+
+pub fn try_create_from_string(expression: String, offset: usize, _context: &ParsingContext) -> NodeCreateResult {
+    let is_first_char_minus = match expression.chars().next() {
+        None => false,
+        Some(c) => '-' == c
+    };
+    if !is_first_char_minus {
+        return NodeCreateResult::None;
+    }
+    match literal::try_create_min_int_literal(&expression[1..], offset + 1) {
+        Some((node, cursor)) => NodeCreateResult::Some((node, cursor + 1)),
+        None => NodeCreateResult::Some((Box::new(Negation::new()), 1)),
+    }
+}
+
+impl Node for Negation {
+    fn evaluate(&self, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        let operand = match &self.operand {
+            Some(o) => arithmetic::to_number(o.evaluate(context)?),
+            None => return Err(EvaluationError::new(String::from("Operand is not defined"))),
+        };
+
+        if let Some(value) = operand.get_int_value() {
+            match value.checked_neg() {
+                Some(v) => Ok(Variable::new_from_int(v)),
+                None => Err(EvaluationError::new(format!("Cannot negate the value: {}", value))),
+            }
+        } else if let Some(value) = operand.get_float_value() {
+            Ok(Variable::new_from_float(-value))
+        } else {
+            Err(EvaluationError::new(String::from("Unsupported type of operand for negation operator")))
+        }
+    }
+
+    fn is_operator(&self) -> bool {
+        false
+    }
+
+    fn set_binary_operands(&mut self, _operands: BinaryOperands) {
+
+    }
+
+    fn get_type(&self) -> &str {
+        "negation"
+    }
+
+    fn get_precedence(&self) -> usize {
+        precedence::UNARY_ARITHMETIC
+    }
+
+    fn is_unary_operator(&self) -> bool {
+        true
+    }
+
+    fn set_unary_operand(&mut self, operand: UnaryOperand) {
+        self.operand = operand;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::render_context::RenderContext;
+    use crate::expressions::nodes::general::literal::Literal;
+
+    #[test]
+    fn test_expressions_node_unary_negation_try_create_from_string_valid() {
+        match try_create_from_string(String::from("-5"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some(_) => {},
+            NodeCreateResult::None => panic!("Expected an operator, got None"),
+            NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_expressions_node_unary_negation_int_float() {
+        let mut operator = Negation::new();
+        operator.set_unary_operand(Some(Box::from(Literal::new_from_int(5))));
+        let param = match operator.evaluate(&RenderContext::new()) {
+            Ok(p) => p,
+            Err(e) => panic!("Expected an integer-type parameter, got an error: {}", e),
+        };
+        assert_eq!(param.get_int_value(), Some(-5));
+
+        let mut operator = Negation::new();
+        operator.set_unary_operand(Some(Box::from(Literal::new_from_float(-2.5))));
+        let param = match operator.evaluate(&RenderContext::new()) {
+            Ok(p) => p,
+            Err(e) => panic!("Expected a float-type parameter, got an error: {}", e),
+        };
+        assert_eq!(param.get_float_value(), Some(2.5));
+    }
+
+    #[test]
+    fn test_expressions_node_unary_negation_string() {
+        let mut operator = Negation::new();
+        operator.set_unary_operand(Some(Box::from(Literal::new_from_str("Hello"))));
+        let err = match operator.evaluate(&RenderContext::new()) {
+            Ok(_) => panic!("Expected an error, but got a parameter"),
+            Err(e) => e,
+        };
+        assert_eq!(err.message, "Unsupported type of operand for negation operator");
+    }
+}
@@ -0,0 +1,131 @@
+use crate::context::render_context::RenderContext;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::nodes::{BinaryOperands, Node, NodeCreateResult, UnaryOperand};
+use crate::expressions::nodes::precedence;
+use crate::common::variable::Variable;
+use crate::context::parsing_context::ParsingContext;
+
+const WORD_FORM: &str = "not";
+const SYMBOL_FORM: &str = "!";
+
+/// Logical negation
+pub struct Not {
+    operand: UnaryOperand,
+}
+
+impl Not {
+    fn new() -> Not {
+        Not{
+            operand: None,
+        }
+    }
+}
+
+pub fn try_create_from_string(expression: String, _offset: usize, _context: &ParsingContext) -> NodeCreateResult {
+    let is_symbol_form = expression.starts_with(SYMBOL_FORM) && !expression.starts_with("!=");
+    let is_word_form = expression.starts_with(WORD_FORM) &&
+        (expression.len() <= WORD_FORM.len() ||
+            !expression[WORD_FORM.len()..].starts_with(|c: char| c.is_alphanumeric() || '_' == c));
+
+    match is_symbol_form || is_word_form {
+        true => {
+            let keyword = if is_symbol_form { SYMBOL_FORM } else { WORD_FORM };
+            NodeCreateResult::Some((Box::new(Not::new()), keyword.len()))
+        },
+        false => NodeCreateResult::None,
+    }
+}
+
+impl Node for Not {
+    fn evaluate(&self, context: &RenderContext) -> Result<Variable, EvaluationError> {
+        let operand = match &self.operand {
+            Some(o) => o.evaluate(context)?,
+            None => return Err(EvaluationError::new(String::from("Operand is not defined"))),
+        };
+
+        Ok(Variable::new_from_boolean(!operand.get_boolean_value()))
+    }
+
+    fn is_operator(&self) -> bool {
+        false
+    }
+
+    fn set_binary_operands(&mut self, _operands: BinaryOperands) {
+
+    }
+
+    fn get_type(&self) -> &str {
+        "not"
+    }
+
+    fn get_precedence(&self) -> usize {
+        precedence::NOT
+    }
+
+    fn is_unary_operator(&self) -> bool {
+        true
+    }
+
+    fn set_unary_operand(&mut self, operand: UnaryOperand) {
+        self.operand = operand;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::render_context::RenderContext;
+    use crate::expressions::nodes::general::literal::Literal;
+
+    #[test]
+    fn test_expressions_node_unary_not_try_create_from_string_valid() {
+        match try_create_from_string(String::from("! flag"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some((_, cursor)) => assert_eq!(cursor, 1),
+            NodeCreateResult::None => panic!("Expected an operator, got None"),
+            NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
+        };
+        match try_create_from_string(String::from("not flag"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some((_, cursor)) => assert_eq!(cursor, 3),
+            NodeCreateResult::None => panic!("Expected an operator, got None"),
+            NodeCreateResult::Err(e) => panic!("Expected an operator, got an error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_expressions_node_unary_not_try_create_from_string_none() {
+        match try_create_from_string(String::from("nothing"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some(_) => panic!("Expected None, got Result"),
+            NodeCreateResult::None => {},
+            NodeCreateResult::Err(e) => panic!("Expected None, got an error: {}", e),
+        };
+        match try_create_from_string(String::from("not_found"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some(_) => panic!("Expected None, got Result"),
+            NodeCreateResult::None => {},
+            NodeCreateResult::Err(e) => panic!("Expected None, got an error: {}", e),
+        };
+        match try_create_from_string(String::from("!= 2"), 0, &ParsingContext::new()) {
+            NodeCreateResult::Some(_) => panic!("Expected None, got Result"),
+            NodeCreateResult::None => {},
+            NodeCreateResult::Err(e) => panic!("Expected None, got an error: {}", e),
+        };
+    }
+
+    #[test]
+    fn test_expressions_node_unary_not_evaluate() {
+        let mut operator = Not::new();
+        operator.set_unary_operand(Some(Box::from(Literal::new_from_bool(true))));
+        let param = match operator.evaluate(&RenderContext::new()) {
+            Ok(p) => p,
+            Err(e) => panic!("Expected a Boolean-type parameter, got an error: {}", e),
+        };
+        assert!(!param.get_boolean_value());
+
+        let mut operator = Not::new();
+        operator.set_unary_operand(Some(Box::from(Literal::new_from_str(""))));
+        let param = match operator.evaluate(&RenderContext::new()) {
+            Ok(p) => p,
+            Err(e) => panic!("Expected a Boolean-type parameter, got an error: {}", e),
+        };
+        assert!(param.get_boolean_value());
+    }
+}
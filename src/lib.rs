@@ -1,7 +1,11 @@
+// Allows `#[derive(ToVariable)]` to refer to this crate as `pete_core` inside of it as well
+extern crate self as pete_core;
+
 pub mod common;
 pub mod context;
 pub mod engine;
 pub mod error;
 pub mod expressions;
+pub mod loaders;
 pub mod nodes;
 pub mod parsers;
\ No newline at end of file
@@ -0,0 +1,53 @@
+use crate::loaders::{LoadedTemplate, LoaderError, TemplateLoader};
+
+/// Tries loaders in the order they are added and returns the first found template,
+/// e.g. templates of a theme overriding default ones
+///
+/// # Examples
+///
+/// ```
+/// use pete_core::loaders::chain::ChainLoader;
+/// use pete_core::loaders::memory::MemoryLoader;
+/// use pete_core::loaders::TemplateLoader;
+///
+/// let mut theme = MemoryLoader::new();
+/// theme.add_template("header.twig", "Theme header");
+/// let mut defaults = MemoryLoader::new();
+/// defaults.add_template("header.twig", "Default header");
+/// defaults.add_template("footer.twig", "Default footer");
+///
+/// let mut loader = ChainLoader::new();
+/// loader.add_loader(theme);
+/// loader.add_loader(defaults);
+/// assert_eq!(loader.load("header.twig").ok().unwrap().source, "Theme header");
+/// assert_eq!(loader.load("footer.twig").ok().unwrap().source, "Default footer");
+/// assert!(loader.load("sidebar.twig").err().unwrap().is_not_found);
+/// ```
+#[derive(Default)]
+pub struct ChainLoader {
+    loaders: Vec<Box<dyn TemplateLoader>>,
+}
+
+impl ChainLoader {
+    pub fn new() -> ChainLoader {
+        ChainLoader {
+            loaders: Vec::new(),
+        }
+    }
+
+    pub fn add_loader<L: TemplateLoader + 'static>(&mut self, loader: L) {
+        self.loaders.push(Box::new(loader));
+    }
+}
+
+impl TemplateLoader for ChainLoader {
+    fn load(&self, name: &str) -> Result<LoadedTemplate, LoaderError> {
+        for loader in &self.loaders {
+            match loader.load(name) {
+                Err(e) if e.is_not_found => continue,
+                result => return result,
+            }
+        }
+        Err(LoaderError::new_not_found(name))
+    }
+}
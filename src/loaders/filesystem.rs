@@ -0,0 +1,99 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+
+use crate::loaders::{LoadedTemplate, LoaderError, TemplateLoader};
+
+/// Templates in files of the given directories. A name is a path relative to a directory, e.g. `emails/welcome.twig`.
+/// Directories are tried in the order they are added
+///
+/// Templates cannot be loaded from outside of the directories: names with `..` or absolute paths are rejected,
+/// as well as symbolic links pointing outside
+pub struct FilesystemLoader {
+    directories: Vec<PathBuf>,
+}
+
+impl FilesystemLoader {
+    pub fn new<P: Into<PathBuf>>(directory: P) -> FilesystemLoader {
+        FilesystemLoader {
+            directories: vec![directory.into()],
+        }
+    }
+
+    pub fn add_directory<P: Into<PathBuf>>(&mut self, directory: P) {
+        self.directories.push(directory.into());
+    }
+
+    fn create_invalid_name_error(name: &str) -> LoaderError {
+        LoaderError::new(format!("Invalid template name \"{}\", it must be a path inside of the template directories", name))
+    }
+
+    // Returns the canonical path of the template in the directory, or `None` if there is no such file
+    fn find_in_directory(directory: &Path, name: &str) -> Result<Option<PathBuf>, LoaderError> {
+        let directory = match fs::canonicalize(directory) {
+            Ok(d) => d,
+            Err(_) => return Ok(None),
+        };
+        let path = match fs::canonicalize(directory.join(name)) {
+            Ok(p) => p,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(LoaderError::new(format!("Failed to load the template \"{}\": {}", name, e))),
+        };
+        if !path.starts_with(&directory) {
+            return Err(FilesystemLoader::create_invalid_name_error(name));
+        }
+        Ok(if path.is_file() { Some(path) } else { None })
+    }
+}
+
+impl TemplateLoader for FilesystemLoader {
+    fn load(&self, name: &str) -> Result<LoadedTemplate, LoaderError> {
+        let is_relative = Path::new(name).components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+        if name.is_empty() || !is_relative {
+            return Err(FilesystemLoader::create_invalid_name_error(name));
+        }
+
+        for directory in &self.directories {
+            let path = match FilesystemLoader::find_in_directory(directory, name)? {
+                Some(path) => path,
+                None => continue,
+            };
+            let source = fs::read_to_string(&path)
+                .map_err(|e| LoaderError::new(format!("Failed to load the template \"{}\": {}", name, e)))?;
+            let last_modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            return Ok(LoadedTemplate::new(path.to_string_lossy().into_owned(), source, last_modified));
+        }
+        Err(LoaderError::new_not_found(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loaders_filesystem_load() {
+        let mut loader = FilesystemLoader::new("tests/templates/missing");
+        loader.add_directory("tests/templates/includes/partials");
+        loader.add_directory("tests/templates/includes");
+
+        let template = loader.load("item.twig").ok().unwrap();
+        assert_eq!(template.source, "<li>{{ loop.index }}. {{ item }}</li>");
+        assert!(template.cache_key.ends_with("item.twig"));
+        assert!(template.last_modified.is_some());
+        assert!(loader.load("./partials/item.twig").is_ok());
+
+        assert!(loader.load("unknown.twig").err().unwrap().is_not_found);
+        assert!(loader.load("partials").err().unwrap().is_not_found);
+    }
+
+    #[test]
+    fn test_loaders_filesystem_sandbox() {
+        let loader = FilesystemLoader::new("tests/templates/includes/partials");
+        for name in ["../page.input.twig", "/etc/passwd", "a/../../page.input.twig", ""].iter() {
+            let err = loader.load(name).err().unwrap();
+            assert!(!err.is_not_found);
+            assert_eq!(err.message, format!("Invalid template name \"{}\", it must be a path inside of the template directories", name));
+        }
+    }
+}
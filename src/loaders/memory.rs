@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use crate::loaders::{LoadedTemplate, LoaderError, TemplateLoader};
+
+/// Templates kept in memory by names, e.g. for tests
+///
+/// # Examples
+///
+/// ```
+/// use pete_core::loaders::memory::MemoryLoader;
+/// use pete_core::loaders::TemplateLoader;
+///
+/// let mut loader = MemoryLoader::new();
+/// loader.add_template("hello.twig", "Hello, {{ name }}!");
+/// assert_eq!(loader.load("hello.twig").ok().unwrap().source, "Hello, {{ name }}!");
+/// assert!(loader.load("bye.twig").err().unwrap().is_not_found);
+/// ```
+#[derive(Default)]
+pub struct MemoryLoader {
+    templates: HashMap<String, String>,
+}
+
+impl MemoryLoader {
+    pub fn new() -> MemoryLoader {
+        MemoryLoader {
+            templates: HashMap::new(),
+        }
+    }
+
+    /// Adds a template. A template with the same name is replaced
+    pub fn add_template(&mut self, name: &str, source: &str) {
+        self.templates.insert(String::from(name), String::from(source));
+    }
+}
+
+impl From<HashMap<String, String>> for MemoryLoader {
+    fn from(templates: HashMap<String, String>) -> MemoryLoader {
+        MemoryLoader {
+            templates,
+        }
+    }
+}
+
+impl TemplateLoader for MemoryLoader {
+    fn load(&self, name: &str) -> Result<LoadedTemplate, LoaderError> {
+        match self.templates.get(name) {
+            // Templates cannot be changed after the loader is set to an engine
+            Some(source) => Ok(LoadedTemplate::new(format!("memory:{}", name), source.clone(), None)),
+            None => Err(LoaderError::new_not_found(name)),
+        }
+    }
+}
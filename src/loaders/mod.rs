@@ -0,0 +1,145 @@
+//! Loading templates by names, e.g. for `{% include 'partials/header.twig' %}` and `Engine::render_name`
+
+pub mod chain;
+pub mod filesystem;
+pub mod memory;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use crate::error::template_error::TemplateError;
+use crate::nodes::Node;
+
+/// A template returned by a loader
+pub struct LoadedTemplate {
+    /// Identifies the template among templates of all loaders, e.g. a path of its file
+    pub cache_key: String,
+    /// Time of the last change, `None` if the template never changes
+    pub last_modified: Option<SystemTime>,
+    pub source: String,
+}
+
+impl LoadedTemplate {
+    pub fn new(cache_key: String, source: String, last_modified: Option<SystemTime>) -> LoadedTemplate {
+        LoadedTemplate {
+            cache_key,
+            last_modified,
+            source,
+        }
+    }
+}
+
+/// An error of loading a template
+#[derive(Debug)]
+pub struct LoaderError {
+    pub message: String,
+    /// The template does not exist, so it is skipped by `{% include ... ignore missing %}`
+    pub is_not_found: bool,
+}
+
+impl LoaderError {
+    pub fn new(message: String) -> LoaderError {
+        LoaderError {
+            message,
+            is_not_found: false,
+        }
+    }
+
+    pub fn new_not_found(name: &str) -> LoaderError {
+        LoaderError {
+            message: format!("Template not found: {}", name),
+            is_not_found: true,
+        }
+    }
+}
+
+impl fmt::Display for LoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for LoaderError {}
+
+/// Finds templates by names. Set with `Engine::set_loader`
+pub trait TemplateLoader: Send + Sync {
+    /// Returns the template or `LoaderError::new_not_found` if there is no template with this name
+    fn load(&self, name: &str) -> Result<LoadedTemplate, LoaderError>;
+}
+
+struct CachedTemplate {
+    last_modified: Option<SystemTime>,
+    node: Arc<dyn Node>,
+}
+
+/// Built templates by cache keys of their loaders. A template is built again when its last modification time changes
+#[derive(Default)]
+pub struct TemplateCache {
+    templates: Mutex<HashMap<String, CachedTemplate>>,
+}
+
+impl TemplateCache {
+    pub fn new() -> TemplateCache {
+        TemplateCache {
+            templates: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the built template from the cache, or builds it with `build` and caches it
+    pub fn get_or_build<F>(&self, template: &LoadedTemplate, build: F) -> Result<Arc<dyn Node>, TemplateError>
+        where F: FnOnce(&str) -> Result<Box<dyn Node>, TemplateError> {
+        if let Some(cached) = self.lock().get(&template.cache_key) {
+            if cached.last_modified == template.last_modified {
+                return Ok(cached.node.clone());
+            }
+        }
+        // The lock is not held while building, a template built twice concurrently is cached once
+        let node: Arc<dyn Node> = Arc::from(build(&template.source)?);
+        self.lock().insert(template.cache_key.clone(), CachedTemplate {
+            last_modified: template.last_modified,
+            node: node.clone(),
+        });
+        Ok(node)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, CachedTemplate>> {
+        // A panic while the lock is held cannot leave the map half updated
+        match self.templates.lock() {
+            Ok(templates) => templates,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use crate::nodes::container::ContainerNode;
+
+    #[test]
+    fn test_loaders_template_cache() {
+        let cache = TemplateCache::new();
+        let mut builds = 0;
+        let mut template = LoadedTemplate::new(String::from("a.twig"), String::from("A"), Some(SystemTime::UNIX_EPOCH));
+        for _ in 0..2 {
+            assert!(cache.get_or_build(&template, |_| { builds += 1; Ok(Box::new(ContainerNode::create())) }).is_ok());
+        }
+        assert_eq!(builds, 1);
+
+        // A modified template is built again
+        template.last_modified = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1));
+        assert!(cache.get_or_build(&template, |_| { builds += 1; Ok(Box::new(ContainerNode::create())) }).is_ok());
+        assert_eq!(builds, 2);
+
+        // Build errors are not cached
+        template.cache_key = String::from("b.twig");
+        let result = cache.get_or_build(&template, |source| Err(TemplateError::create(String::from(source), 0, String::from("Broken"))));
+        assert_eq!(result.err().unwrap().message, "Broken");
+        assert!(cache.get_or_build(&template, |_| { builds += 1; Ok(Box::new(ContainerNode::create())) }).is_ok());
+        assert_eq!(builds, 3);
+    }
+}
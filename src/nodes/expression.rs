@@ -14,6 +14,8 @@ pub struct ExpressionNode {
     base_node: BaseNode,
     build_context: BuildContext,
     expression_node: Box<dyn expression_nodes::Node>,
+    // Offset of the expression in the template
+    expression_offset: usize,
 }
 
 impl ExpressionNode {
@@ -22,6 +24,7 @@ impl ExpressionNode {
             base_node: BaseNode::new(),
             build_context: BuildContext::new(),
             expression_node: Box::new(Literal::new_from_str("")),
+            expression_offset: 0,
         }
     }
 
@@ -42,28 +45,32 @@ impl Node for ExpressionNode {
     fn build(&mut self, context: &BuildContext) -> NodeBuildResult {
         self.base_node.has_nolinebreak_beginning = context.template_remain[2..3].to_string() == "-";
         self.build_context = context.clone();
-        let end_pos_with_tag = match expression_mod::get_end_offset(&context.template_remain, EXPRESSION_END) {
+        let end_pos_with_tag = match expression_mod::get_end_offset(&context.template_remain[EXPRESSION_START.len()..], EXPRESSION_END) {
             None => Err(TemplateError::create(
                 context.template.clone(),
                 context.offset,
                 String::from("Expression is not closed"))),
-            Some(pos) => Ok(pos),
+            Some(pos) => Ok(EXPRESSION_START.len() + pos),
         }?;
 
-        let end_pos = end_pos_with_tag - EXPRESSION_END.len();
+        // position of the closing tag
+        let end_pos = end_pos_with_tag + 1 - EXPRESSION_END.len();
         self.base_node.end_offset = context.offset + end_pos_with_tag;
-        self.base_node.has_nolinebreak_end = context.template_remain[end_pos-1..end_pos].to_string() == "-";
+        self.base_node.has_nolinebreak_end = context.template_remain[..end_pos].ends_with('-');
         self.base_node.start_offset = context.offset;
-        let expression_string = context.template_remain[EXPRESSION_START.len()..end_pos].to_string();
-        let expr_node = match expression_mod::parse(expression_string) {
+        let expression_start = EXPRESSION_START.len() + if self.base_node.has_nolinebreak_beginning { 1 } else { 0 };
+        let expression_end = if self.base_node.has_nolinebreak_end { end_pos - 1 } else { end_pos };
+        let expression_string = context.template_remain[expression_start..expression_end.max(expression_start)].to_string();
+        let expr_node = match expression_mod::parse(expression_string, &context.parsing_context) {
             Ok(n) => Ok(n),
             Err(err) => Err(TemplateError::create(
                 self.build_context.template.clone(),
-                self.build_context.offset,
+                self.build_context.offset + expression_start + err.offset,
                 String::from(format!("Failed to build an expression: {}", err.message))
             )),
         }?;
         self.expression_node = expr_node;
+        self.expression_offset = self.build_context.offset + expression_start;
         Ok(NodeBuildData::new(end_pos_with_tag, false, self.base_node.has_nolinebreak_end))
     }
 
@@ -72,11 +79,14 @@ impl Node for ExpressionNode {
     }
 
     fn render(&self, context: &mut RenderContext) -> RenderResult {
-        match self.expression_node.evaluate(&context) {
+        match self.expression_node.evaluate(context).and_then(|parameter| context.resolve_nested(parameter)) {
             Ok(parameter) => RenderResult::Ok(parameter.as_string()),
             Err(err) => RenderResult::Err(TemplateError::create(
                 self.build_context.template.clone(),
-                self.build_context.offset,
+                match err.offset {
+                    Some(offset) => self.expression_offset + offset,
+                    None => self.build_context.offset,
+                },
                 String::from(format!("Failed to evaluate an expression: {}", err.message))
             )),
         }
@@ -15,7 +15,7 @@ const COMMENT_START: &str = "{#";
 const COMMENT_END: &str = "#}";
 const DYNAMIC_BLOCK_STARTS: [&str; 3] = [EXPRESSION_START, TAG_START, COMMENT_START];
 
-pub trait Node: HasBaseNodeTrait {
+pub trait Node: HasBaseNodeTrait + Send + Sync {
     fn add_child(&mut self, child: Box<dyn Node>);
     fn build(&mut self, context: &BuildContext) -> NodeBuildResult;
     fn is_continuation(&self, context: &BuildContext) -> bool;
@@ -6,7 +6,7 @@ use crate::expressions::nodes::{Node as ExpressionNode};
 use crate::expressions::nodes::general::literal::Literal;
 use crate::error::template_error::TemplateError;
 use crate::nodes::{BaseNode, Node};
-use crate::nodes::tags::TAG_END;
+use crate::nodes::tags::{TAG_END, TAG_START};
 use crate::nodes::container::ContainerNode;
 use crate::parsers::expression_parser::{ parse_expression_string, ParseExpressionStringResult };
 use crate::parsers::tag_parser::{get_keyword, GetKeywordResult};
@@ -23,6 +23,8 @@ pub struct ConditionNode {
     base_node: BaseNode,
     // Indexes of expressions match to indexes of children nodes
     expressions: Vec<Box<dyn ExpressionNode>>,
+    // Offsets of expressions in the template, used in evaluation errors
+    expression_offsets: Vec<usize>,
 }
 
 impl ConditionNode {
@@ -30,6 +32,7 @@ impl ConditionNode {
         ConditionNode{
             base_node: BaseNode::new(),
             expressions: Vec::new(),
+            expression_offsets: Vec::new(),
         }
     }
 
@@ -55,11 +58,12 @@ impl ConditionNode {
                 s))
         }?;
 
-        match expressions::parse(parsed_expression.expression_string.clone()) {
+        match expressions::parse(parsed_expression.expression_string.clone(), &context.parsing_context) {
             Ok(expr_node) => {
                 self.expressions.push(expr_node);
+                self.expression_offsets.push(context.offset + get_keyword_result.end_pos);
                 let mut container = ContainerNode::create();
-                let mut container_base_node = container.get_base_node_mut();
+                let container_base_node = container.get_base_node_mut();
                 container_base_node.has_nolinebreak_beginning = get_keyword_result.has_nolinebreak_beginning;
                 container_base_node.has_nolinebreak_end = parsed_expression.has_nolinebreak_end;
                 container_base_node.start_offset = context.offset + parsed_expression.end_offset + 1;
@@ -69,7 +73,7 @@ impl ConditionNode {
             },
             Err(err) => Err(TemplateError::create(
                 context.template.clone(),
-                context.offset,
+                context.offset + get_keyword_result.end_pos + err.offset,
                 String::from(format!("An error in the Condition Node. Failed to evaluate an expression: {}", err.message))
             ))
         }
@@ -99,8 +103,9 @@ impl ConditionNode {
         }
 
         self.expressions.push(Box::from(Literal::new_from_bool(true)));
+        self.expression_offsets.push(context.offset);
         let mut container = ContainerNode::create();
-        let mut container_base_node = container.get_base_node_mut();
+        let container_base_node = container.get_base_node_mut();
         container_base_node.start_offset = context.offset + parsed_expression.end_offset + 1;
         container_base_node.end_offset = container_base_node.start_offset;
         container_base_node.has_nolinebreak_beginning = get_keyword_result.has_nolinebreak_beginning;
@@ -110,8 +115,9 @@ impl ConditionNode {
     }
 
     fn build_block_end(&mut self, context: &BuildContext) -> NodeBuildResult {
-        match expressions::get_end_offset(&context.template_remain, TAG_END) {
+        match expressions::get_end_offset(&context.template_remain[TAG_START.len()..], TAG_END) {
             Some(end_pos) => {
+                let end_pos = TAG_START.len() + end_pos;
                 let has_nolinebreak_end = context.template_remain[..end_pos-TAG_END.len()+1].ends_with('-');
                 self.base_node.end_offset = context.offset + end_pos;
                 self.base_node.has_nolinebreak_end = has_nolinebreak_end;
@@ -203,7 +209,10 @@ impl Node for ConditionNode {
                 },
                 Err(err) => Some(RenderResult::Err(TemplateError::create(
                     context.template.clone(),
-                    context.offset,
+                    match err.offset {
+                        Some(offset) => self.expression_offsets[i] + offset,
+                        None => self.base_node.start_offset,
+                    },
                     String::from(format!("Failed to evaluate an expression: {}", err.message))
                 ))),
             };
@@ -0,0 +1,393 @@
+use crate::common::variable::{Variable, VariableStore};
+use crate::context::build_context::BuildContext;
+use crate::context::render_context::RenderContext;
+use crate::engine::{NodeBuildResult, NodeBuildData, RenderResult};
+use crate::expressions;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::nodes::{Node as ExpressionNode};
+use crate::error::template_error::TemplateError;
+use crate::nodes::{BaseNode, Node};
+use crate::nodes::tags::{render_block, TAG_END, TAG_START};
+use crate::nodes::container::ContainerNode;
+use crate::parsers::expression_parser::{ parse_expression_string, ParseExpressionStringResult };
+use crate::parsers::tag_parser::{get_keyword, split_names, GetKeywordResult};
+
+use derive_macro::HasBaseNode;
+
+const FOR_KEYWORD: &str = "for";
+const ELSE_KEYWORD: &str = "else";
+const ENDFOR_KEYWORD: &str = "endfor";
+
+const IN_SEPARATOR: &str = " in ";
+const IF_SEPARATOR: &str = " if ";
+const LOOP_VARIABLE: &str = "loop";
+
+/// `{% for item in items %}`, `{% for key, value in map if value %}`.
+/// The first child is the loop body, the second one is the optional `{% else %}` block rendered for an empty sequence
+#[derive(HasBaseNode)]
+pub struct ForLoopNode {
+    base_node: BaseNode,
+    // Names of the value, or of the key and the value
+    targets: Vec<String>,
+    sequence: Option<Box<dyn ExpressionNode>>,
+    sequence_offset: usize,
+    // The inline `if` clause
+    condition: Option<Box<dyn ExpressionNode>>,
+    condition_offset: usize,
+}
+
+impl ForLoopNode {
+    fn create() -> ForLoopNode {
+        ForLoopNode {
+            base_node: BaseNode::new(),
+            targets: Vec::new(),
+            sequence: None,
+            sequence_offset: 0,
+            condition: None,
+            condition_offset: 0,
+        }
+    }
+
+    pub fn try_create_from_template(template: &String) -> Option<Box<dyn Node>> {
+        let keyword = match get_keyword(template) {
+            Some(r) => r.keyword,
+            None => return None,
+        };
+        match keyword {
+            FOR_KEYWORD => Some(Box::from(ForLoopNode::create())),
+            _ => None
+        }
+    }
+
+    fn parse_expression(&self, context: &BuildContext, string: &str, offset: usize) -> Result<Box<dyn ExpressionNode>, TemplateError> {
+        expressions::parse(String::from(string), &context.parsing_context).map_err(|err| TemplateError::create(
+            context.template.clone(),
+            offset + err.offset,
+            format!("An error in the For Node. Failed to parse an expression: {}", err.message)
+        ))
+    }
+
+    fn build_block_for(&mut self, context: &BuildContext, get_keyword_result: &GetKeywordResult) -> NodeBuildResult {
+        self.base_node.start_offset = context.offset;
+        let parsed_expression = match parse_expression_string(&get_keyword_result.remain,
+                get_keyword_result.end_pos, TAG_END) {
+            Ok(s) => Ok(s) as Result<ParseExpressionStringResult, TemplateError>,
+            Err(s) => return Err(TemplateError::create(
+                context.template.clone(),
+                context.offset,
+                s))
+        }?;
+
+        let header = &parsed_expression.expression_string;
+        let header_offset = context.offset + get_keyword_result.end_pos;
+        let in_end = match expressions::get_end_offset(header, IN_SEPARATOR) {
+            Some(pos) => pos + 1,
+            None => return Err(TemplateError::create(
+                context.template.clone(),
+                header_offset,
+                String::from("An error in the For Node. Expected: for item in items"))),
+        };
+        self.targets = match split_names(&header[..in_end - IN_SEPARATOR.len()]) {
+            Some(targets) if targets.len() <= 2 => targets,
+            _ => return Err(TemplateError::create(
+                context.template.clone(),
+                header_offset,
+                format!("An error in the For Node. Expected a name or a key and a value name, got: {}", header[..in_end - IN_SEPARATOR.len()].trim()))),
+        };
+
+        let remain = &header[in_end..];
+        self.sequence_offset = header_offset + in_end;
+        let sequence_string = match expressions::get_end_offset(remain, IF_SEPARATOR) {
+            Some(pos) => {
+                let condition_start = pos + 1;
+                self.condition_offset = self.sequence_offset + condition_start;
+                self.condition = Some(self.parse_expression(context, &remain[condition_start..], self.condition_offset)?);
+                &remain[..condition_start - IF_SEPARATOR.len()]
+            },
+            None => remain,
+        };
+        self.sequence = Some(self.parse_expression(context, sequence_string, self.sequence_offset)?);
+
+        self.push_container(context, &parsed_expression);
+        Ok(NodeBuildData::new(parsed_expression.end_offset, true, parsed_expression.has_nolinebreak_end))
+    }
+
+    fn build_block_else(&mut self, context: &BuildContext, get_keyword_result: &GetKeywordResult) -> NodeBuildResult {
+        let parsed_expression = match parse_expression_string(&get_keyword_result.remain, get_keyword_result.end_pos, TAG_END) {
+            Ok(s) => Ok(s) as Result<ParseExpressionStringResult, TemplateError>,
+            Err(s) => return Err(TemplateError::create(
+                context.template.clone(),
+                context.offset,
+                s))
+        }?;
+
+        if !parsed_expression.expression_string.trim_matches(' ').is_empty() {
+            return Err(TemplateError::create(
+                context.template.clone(),
+                context.offset,
+                format!("An error in the For Node. Unexpected characters in ELSE block: {}", parsed_expression.expression_string)
+            ))
+        }
+        if self.base_node.children.len() > 1 {
+            return Err(TemplateError::create(
+                context.template.clone(),
+                context.offset,
+                String::from("An error in the For Node. Only one ELSE block is allowed")
+            ))
+        }
+
+        self.push_container(context, &parsed_expression);
+        Ok(NodeBuildData::new(parsed_expression.end_offset, true, parsed_expression.has_nolinebreak_end))
+    }
+
+    fn push_container(&mut self, context: &BuildContext, parsed_expression: &ParseExpressionStringResult) {
+        // A block starts after `-%}` of its tag, and ends before `{%-` of the next tag, see `build`
+        let mut container = ContainerNode::create();
+        let container_base_node = container.get_base_node_mut();
+        container_base_node.has_nolinebreak_beginning = parsed_expression.has_nolinebreak_end;
+        container_base_node.start_offset = context.offset + parsed_expression.end_offset + 1;
+        container_base_node.end_offset = container_base_node.start_offset;
+        self.base_node.children.push(Box::from(container));
+    }
+
+    fn build_block_end(&mut self, context: &BuildContext) -> NodeBuildResult {
+        match expressions::get_end_offset(&context.template_remain[TAG_START.len()..], TAG_END) {
+            Some(end_pos) => {
+                let end_pos = TAG_START.len() + end_pos;
+                let has_nolinebreak_end = context.template_remain[..end_pos-TAG_END.len()+1].ends_with('-');
+                self.base_node.end_offset = context.offset + end_pos;
+                self.base_node.has_nolinebreak_end = has_nolinebreak_end;
+                Ok(NodeBuildData::new(end_pos, false, has_nolinebreak_end))
+            },
+            None => Err(TemplateError::create(
+                context.template.clone(),
+                context.offset,
+                String::from("Cannot find closing tag."))),
+        }
+    }
+
+    fn create_evaluation_error(&self, context: &RenderContext, expression_offset: usize, err: EvaluationError) -> TemplateError {
+        TemplateError::create(
+            context.template.clone(),
+            match err.offset {
+                Some(offset) => expression_offset + offset,
+                None => self.base_node.start_offset,
+            },
+            format!("Failed to evaluate an expression: {}", err.message)
+        )
+    }
+
+    // Pairs of keys and values of the sequence. Keys of a list are indexes of its items
+    fn get_items(&self, context: &RenderContext) -> Result<Vec<(Variable, Variable)>, TemplateError> {
+        let sequence = match &self.sequence {
+            Some(sequence) => sequence.evaluate(context),
+            None => Ok(Variable::Null),
+        };
+        match sequence.map_err(|err| self.create_evaluation_error(context, self.sequence_offset, err))? {
+            Variable::List(items) => Ok(items.into_iter().enumerate()
+                .map(|(i, item)| (Variable::from(i), item))
+                .collect()),
+            Variable::Map(store) => Ok(store.into_iter()
+                .map(|(key, value)| (Variable::from(key), value))
+                .collect()),
+            Variable::Null => Ok(Vec::new()),
+            other => Err(TemplateError::create(
+                context.template.clone(),
+                self.sequence_offset,
+                format!("Cannot iterate over a value of type \"{}\"", other.get_type_name()))),
+        }
+    }
+
+    fn assign_targets(&self, context: &mut RenderContext, key: &Variable, value: &Variable) {
+        match self.targets.as_slice() {
+            [value_name] => context.set_local(value_name, value.clone()),
+            [key_name, value_name] => {
+                context.set_local(key_name, key.clone());
+                context.set_local(value_name, value.clone());
+            },
+            _ => {},
+        }
+    }
+
+    // Renders the body for each item in the scope of the loop. Returns `None` if no item passes the `if` clause
+    fn render_items(&self, items: Vec<(Variable, Variable)>, parent: Variable, context: &mut RenderContext) -> Result<Option<String>, TemplateError> {
+        let mut items = items;
+        if let Some(condition) = &self.condition {
+            let mut filtered = Vec::new();
+            for (key, value) in items {
+                self.assign_targets(context, &key, &value);
+                match condition.evaluate(context) {
+                    Ok(v) => if v.get_boolean_value() { filtered.push((key, value)) },
+                    Err(err) => return Err(self.create_evaluation_error(context, self.condition_offset, err)),
+                }
+            }
+            items = filtered;
+        }
+
+        let body = match self.base_node.children.first() {
+            Some(body) if !items.is_empty() => body,
+            _ => return Ok(None),
+        };
+        let length = items.len();
+        let mut result = String::new();
+        for (index, (key, value)) in items.iter().enumerate() {
+            let mut metadata = VariableStore::new();
+            metadata.insert(String::from("index"), Variable::from(index + 1));
+            metadata.insert(String::from("index0"), Variable::from(index));
+            metadata.insert(String::from("first"), Variable::from(index == 0));
+            metadata.insert(String::from("last"), Variable::from(index + 1 == length));
+            metadata.insert(String::from("length"), Variable::from(length));
+            metadata.insert(String::from("parent"), parent.clone());
+            context.set_local(LOOP_VARIABLE, Variable::Map(metadata));
+            self.assign_targets(context, key, value);
+            result += render_block(body.as_ref(), context)?.as_str();
+        }
+        Ok(Some(result))
+    }
+}
+
+impl Node for ForLoopNode {
+    fn add_child(&mut self, child: Box<dyn Node>) {
+        match self.base_node.children.last_mut() {
+            None => {},
+            Some(c) => {
+                c.get_base_node_mut().set_end_offset(child.get_base_node().end_offset);
+                c.add_child(child);
+            },
+        }
+    }
+
+    fn build(&mut self, context: &BuildContext) -> NodeBuildResult {
+        let result = match get_keyword(&context.template_remain) {
+            Some(r) => Ok(r),
+            None => Err(TemplateError::create(
+                context.template.clone(),
+                context.offset,
+                String::from("Unknown keyword. Expected: (for|else|endfor)"))),
+        }?;
+
+        match self.get_children_mut().last_mut() {
+            Some(child) => child.get_base_node_mut().has_nolinebreak_end = result.has_nolinebreak_beginning,
+            None => self.get_base_node_mut().has_nolinebreak_beginning = result.has_nolinebreak_beginning,
+        };
+        match result.keyword {
+            FOR_KEYWORD => self.build_block_for(context, &result),
+            ELSE_KEYWORD => self.build_block_else(context, &result),
+            ENDFOR_KEYWORD => self.build_block_end(context),
+            _ => Err(TemplateError::create(
+                context.template.clone(),
+                context.offset,
+                String::from("Unknown keyword. Expected: (for|else|endfor)"))),
+        }
+    }
+
+    fn is_continuation(&self, context: &BuildContext) -> bool {
+        let keyword = match get_keyword(&context.template_remain) {
+            Some(r) => r.keyword,
+            None => "",
+        };
+        ELSE_KEYWORD == keyword || ENDFOR_KEYWORD == keyword
+    }
+
+    fn render(&self, context: &mut RenderContext) -> RenderResult {
+        let items = self.get_items(context)?;
+        let parent = Variable::new_from_object(context.get_scope_view());
+
+        // Variables assigned in the loop are removed after it, except variables defined before the loop
+        context.push_scope();
+        let result = self.render_items(items, parent, context);
+        context.pop_scope();
+
+        match result? {
+            Some(result) => Ok(result),
+            None => match self.base_node.children.get(1) {
+                Some(else_block) => render_block(else_block.as_ref(), context),
+                None => Ok(String::new()),
+            },
+        }
+    }
+
+    fn get_name(&self) -> &str {
+        "for"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes::expression::ExpressionNode as TemplateExpressionNode;
+    use crate::nodes::static_node::StaticNode;
+
+    fn build(template: &str) -> ForLoopNode {
+        let mut node = ForLoopNode::create();
+        let mut context = BuildContext::new();
+        context.template = String::from(template);
+        context.template_remain = String::from(template);
+        match node.build(&context) {
+            Ok(data) => assert!(data.is_nesting_started),
+            Err(e) => panic!("Failed to build a node: {}", e.message),
+        }
+        node
+    }
+
+    fn add_child(node: &mut ForLoopNode, mut child: Box<dyn Node>, template: &str) {
+        let mut context = BuildContext::new();
+        context.template_remain = String::from(template);
+        child.build(&context).unwrap();
+        node.add_child(child);
+    }
+
+    #[test]
+    fn test_nodes_tags_for_loop_try_create() {
+        assert!(ForLoopNode::try_create_from_template(&String::from("{% for x in a %}")).is_some());
+        assert!(ForLoopNode::try_create_from_template(&String::from("{%- for x in a %}")).is_some());
+        assert!(ForLoopNode::try_create_from_template(&String::from("{% if x %}")).is_none());
+    }
+
+    #[test]
+    fn test_nodes_tags_for_loop_build() {
+        let node = build("{% for key, value in {a: 1} if value > 0 %}");
+        assert_eq!(node.targets, vec!["key", "value"]);
+        assert_eq!(node.sequence_offset, 21);
+        assert_eq!(node.condition_offset, 31);
+        assert!(node.condition.is_some());
+
+        let mut node = ForLoopNode::create();
+        let mut context = BuildContext::new();
+        context.template_remain = String::from("{% for a, b, c in items %}");
+        match node.build(&context) {
+            Ok(_) => panic!("Building must have failed."),
+            Err(e) => assert_eq!(e.message, "An error in the For Node. Expected a name or a key and a value name, got: a, b, c"),
+        }
+        context.template_remain = String::from("{% for item %}");
+        match ForLoopNode::create().build(&context) {
+            Ok(_) => panic!("Building must have failed."),
+            Err(e) => assert_eq!(e.message, "An error in the For Node. Expected: for item in items"),
+        }
+    }
+
+    #[test]
+    fn test_nodes_tags_for_loop_render() {
+        let mut node = build("{% for item in items %}");
+        add_child(&mut node, TemplateExpressionNode::try_create_from_template(&String::from("{{ loop.index }}")).unwrap(), "{{ loop.index }}");
+        add_child(&mut node, StaticNode::try_create_from_template(&String::from(":")).unwrap(), ":");
+        add_child(&mut node, TemplateExpressionNode::try_create_from_template(&String::from("{{ item }}")).unwrap(), "{{ item }}");
+
+        let mut context = RenderContext::new();
+        context.set_parameter("item", Variable::new_from_str("outer"));
+        context.set_parameter("items", Variable::from(vec!["a", "b"]));
+        match node.render(&mut context) {
+            Ok(string) => assert_eq!(string, "1:a2:b"),
+            Err(e) => panic!("Expected to render a node, but got an error: {}", e),
+        }
+        // Loop variables are removed after the loop
+        assert_eq!(context.get_variable("item"), Some(&Variable::new_from_str("outer")));
+        assert!(context.get_variable("loop").is_none());
+
+        context.set_parameter("items", Variable::new_from_int(1));
+        match node.render(&mut context) {
+            Ok(_) => panic!("Rendering must have failed."),
+            Err(e) => assert_eq!(e.message, "Cannot iterate over a value of type \"int\""),
+        }
+    }
+}
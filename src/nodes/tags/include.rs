@@ -0,0 +1,256 @@
+use crate::common::variable::{Variable, VariableStore};
+use crate::context::build_context::BuildContext;
+use crate::context::render_context::RenderContext;
+use crate::engine::{self, NodeBuildResult, NodeBuildData, RenderResult};
+use crate::expressions;
+use crate::expressions::errors::evaluation_error::EvaluationError;
+use crate::expressions::nodes::{Node as ExpressionNode};
+use crate::error::template_error::TemplateError;
+use crate::nodes::{BaseNode, Node};
+use crate::nodes::tags::TAG_END;
+use crate::parsers::expression_parser::{ parse_expression_string, ParseExpressionStringResult };
+use crate::parsers::tag_parser::{get_keyword, GetKeywordResult};
+
+use derive_macro::HasBaseNode;
+
+const INCLUDE_KEYWORD: &str = "include";
+
+const WITH_SEPARATOR: &str = " with ";
+const ONLY_SUFFIX: &str = " only";
+const IGNORE_MISSING_SUFFIX: &str = " ignore missing";
+
+/// Protects from a template including itself endlessly
+pub const MAX_INCLUDE_DEPTH: usize = 64;
+
+/// An error of `render_include`
+pub enum IncludeError {
+    /// The template cannot be loaded, e.g. it is not found
+    Load(String),
+    /// The included template failed to build or render
+    Template(TemplateError),
+}
+
+/// Loads, builds and renders a template with the loader of the context. Built templates are cached.
+/// A missing template is rendered as an empty string if `ignore_missing` is set
+pub fn render_include(context: &RenderContext, name: &str, parameters: VariableStore, ignore_missing: bool) -> Result<String, IncludeError> {
+    if context.include_depth >= MAX_INCLUDE_DEPTH {
+        return Err(IncludeError::Load(format!("Cannot include \"{}\", templates are nested deeper than {} levels", name, MAX_INCLUDE_DEPTH)));
+    }
+    let loader = match &context.loader {
+        Some(loader) => loader,
+        None => return Err(IncludeError::Load(format!("Cannot load the template \"{}\", no template loader is set", name))),
+    };
+    let loaded = match loader.load(name) {
+        Ok(loaded) => loaded,
+        Err(e) if e.is_not_found && ignore_missing => return Ok(String::new()),
+        Err(e) => return Err(IncludeError::Load(e.message)),
+    };
+
+    let node = context.template_cache.get_or_build(&loaded, |source| engine::build_template(source, &context.parsing_context))
+        .map_err(|e| IncludeError::Template(e.with_filename(name)))?;
+    let mut include_context = context.create_include_context(name, loaded.source, parameters);
+    node.render(&mut include_context)
+        .map_err(|e| IncludeError::Template(e.with_filename(name)))
+}
+
+/// `{% include 'partials/header.twig' with { title: title } only ignore missing %}`.
+/// The included template gets the variables of the current one, unless `only` is set, and the variables of `with`
+#[derive(HasBaseNode)]
+pub struct IncludeNode {
+    base_node: BaseNode,
+    name: Option<Box<dyn ExpressionNode>>,
+    name_offset: usize,
+    variables: Option<Box<dyn ExpressionNode>>,
+    variables_offset: usize,
+    is_only: bool,
+    is_ignore_missing: bool,
+}
+
+impl IncludeNode {
+    fn create() -> IncludeNode {
+        IncludeNode {
+            base_node: BaseNode::new(),
+            name: None,
+            name_offset: 0,
+            variables: None,
+            variables_offset: 0,
+            is_only: false,
+            is_ignore_missing: false,
+        }
+    }
+
+    pub fn try_create_from_template(template: &String) -> Option<Box<dyn Node>> {
+        let keyword = match get_keyword(template) {
+            Some(r) => r.keyword,
+            None => return None,
+        };
+        match keyword {
+            INCLUDE_KEYWORD => Some(Box::from(IncludeNode::create())),
+            _ => None
+        }
+    }
+
+    // Removes `only` and `ignore missing` from the end of the string, in any order
+    fn strip_options<'a>(&mut self, string: &'a str) -> &'a str {
+        let mut string = string.trim_end();
+        loop {
+            if let Some(s) = string.strip_suffix(ONLY_SUFFIX) {
+                self.is_only = true;
+                string = s.trim_end();
+            } else if let Some(s) = string.strip_suffix(IGNORE_MISSING_SUFFIX) {
+                self.is_ignore_missing = true;
+                string = s.trim_end();
+            } else {
+                return string;
+            }
+        }
+    }
+
+    fn parse_expression(&self, context: &BuildContext, string: &str, offset: usize) -> Result<Box<dyn ExpressionNode>, TemplateError> {
+        expressions::parse(String::from(string), &context.parsing_context).map_err(|err| TemplateError::create(
+            context.template.clone(),
+            offset + err.offset,
+            format!("An error in the Include Node. Failed to parse an expression: {}", err.message)
+        ))
+    }
+
+    fn build_block_include(&mut self, context: &BuildContext, get_keyword_result: &GetKeywordResult) -> NodeBuildResult {
+        self.base_node.start_offset = context.offset;
+        let parsed_expression = match parse_expression_string(&get_keyword_result.remain,
+                get_keyword_result.end_pos, TAG_END) {
+            Ok(s) => Ok(s) as Result<ParseExpressionStringResult, TemplateError>,
+            Err(s) => return Err(TemplateError::create(
+                context.template.clone(),
+                context.offset,
+                s))
+        }?;
+
+        let header = self.strip_options(&parsed_expression.expression_string);
+        self.name_offset = context.offset + get_keyword_result.end_pos;
+        let name_string = match expressions::get_end_offset(header, WITH_SEPARATOR) {
+            Some(pos) => {
+                let variables_start = pos + 1;
+                self.variables_offset = self.name_offset + variables_start;
+                self.variables = Some(self.parse_expression(context, &header[variables_start..], self.variables_offset)?);
+                // Twig also allows `ignore missing` before `with`
+                self.strip_options(&header[..variables_start - WITH_SEPARATOR.len()])
+            },
+            None => header,
+        };
+        self.name = Some(self.parse_expression(context, name_string, self.name_offset)?);
+
+        self.base_node.end_offset = context.offset + parsed_expression.end_offset;
+        self.base_node.has_nolinebreak_end = parsed_expression.has_nolinebreak_end;
+        Ok(NodeBuildData::new(parsed_expression.end_offset, false, parsed_expression.has_nolinebreak_end))
+    }
+
+    fn evaluate(&self, expression: &Option<Box<dyn ExpressionNode>>, expression_offset: usize, context: &RenderContext) -> Result<Variable, TemplateError> {
+        let result = match expression {
+            Some(expression) => expression.evaluate(context),
+            None => Ok(Variable::Null),
+        };
+        result.map_err(|err: EvaluationError| TemplateError::create(
+            context.template.clone(),
+            match err.offset {
+                Some(offset) => expression_offset + offset,
+                None => self.base_node.start_offset,
+            },
+            format!("Failed to evaluate an expression: {}", err.message)
+        ))
+    }
+}
+
+impl Node for IncludeNode {
+    fn add_child(&mut self, _child: Box<dyn Node>) {
+        panic!("Cannot add a child to include node");
+    }
+
+    fn build(&mut self, context: &BuildContext) -> NodeBuildResult {
+        let result = match get_keyword(&context.template_remain) {
+            Some(r) if INCLUDE_KEYWORD == r.keyword => r,
+            _ => return Err(TemplateError::create(
+                context.template.clone(),
+                context.offset,
+                String::from("Unknown keyword. Expected: include"))),
+        };
+        self.base_node.has_nolinebreak_beginning = result.has_nolinebreak_beginning;
+        self.build_block_include(context, &result)
+    }
+
+    fn is_continuation(&self, _context: &BuildContext) -> bool {
+        false
+    }
+
+    fn render(&self, context: &mut RenderContext) -> RenderResult {
+        let name = match self.evaluate(&self.name, self.name_offset, context)? {
+            Variable::String(name) => name,
+            other => return Err(TemplateError::create(
+                context.template.clone(),
+                self.name_offset,
+                format!("A template name must be a string, got {}", other.get_type_name()))),
+        };
+        let mut parameters = if self.is_only { VariableStore::new() } else { context.get_template_variables() };
+        match self.evaluate(&self.variables, self.variables_offset, context)? {
+            Variable::Map(variables) => parameters.extend(variables),
+            Variable::Null => {},
+            other => return Err(TemplateError::create(
+                context.template.clone(),
+                self.variables_offset,
+                format!("Variables of an included template must be a map, got {}", other.get_type_name()))),
+        }
+
+        match render_include(context, &name, parameters, self.is_ignore_missing) {
+            Ok(result) => Ok(result),
+            Err(IncludeError::Load(message)) => Err(TemplateError::create(
+                context.template.clone(),
+                self.base_node.start_offset,
+                message)),
+            Err(IncludeError::Template(e)) => Err(e),
+        }
+    }
+
+    fn get_name(&self) -> &str {
+        "include"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(template: &str) -> IncludeNode {
+        let mut node = IncludeNode::create();
+        let mut context = BuildContext::new();
+        context.template = String::from(template);
+        context.template_remain = String::from(template);
+        if let Err(e) = node.build(&context) {
+            panic!("Failed to build a node: {}", e.message);
+        }
+        node
+    }
+
+    #[test]
+    fn test_nodes_tags_include_build() {
+        let node = build("{% include 'header.twig' %}");
+        assert!(node.variables.is_none());
+        assert!(!node.is_only && !node.is_ignore_missing);
+
+        let node = build("{% include 'header.twig' with {title: 'Hi'} only ignore missing %}");
+        assert_eq!(node.name_offset, 10);
+        assert_eq!(node.variables_offset, 30);
+        assert!(node.is_only && node.is_ignore_missing);
+
+        let node = build("{% include 'header ' ~ 'only' ignore missing with {a: ' only'} %}");
+        assert!(node.variables.is_some());
+        assert!(!node.is_only && node.is_ignore_missing);
+    }
+
+    #[test]
+    fn test_nodes_tags_include_render_without_loader() {
+        let node = build("{% include 'header.twig' %}");
+        match node.render(&mut RenderContext::new()) {
+            Ok(_) => panic!("Rendering must have failed."),
+            Err(e) => assert_eq!(e.message, "Cannot load the template \"header.twig\", no template loader is set"),
+        }
+    }
+}
@@ -1,13 +1,24 @@
 pub mod condition;
+pub mod for_loop;
+pub mod include;
+pub mod set;
 
+use crate::context::render_context::RenderContext;
+use crate::engine::RenderResult;
 use crate::nodes::{Node, NodeCreator};
 use crate::nodes::tags::condition::ConditionNode;
+use crate::nodes::tags::for_loop::ForLoopNode;
+use crate::nodes::tags::include::IncludeNode;
+use crate::nodes::tags::set::SetNode;
 
 pub const TAG_START: &str = "{%";
 pub const TAG_END: &str = "%}";
 
-pub const NODE_CREATORS: [NodeCreator; 1] = [
+pub const NODE_CREATORS: [NodeCreator; 4] = [
     ConditionNode::try_create_from_template,
+    ForLoopNode::try_create_from_template,
+    IncludeNode::try_create_from_template,
+    SetNode::try_create_from_template,
 ];
 
 
@@ -24,4 +35,15 @@ pub fn try_create_from_template(template: &String) -> Option<Box<dyn Node>> {
     }
     
     None
-}
\ No newline at end of file
+}
+
+/// Renders a block of a tag, e.g. a loop body. The line break before `{%-` of the next tag is removed
+pub fn render_block(block: &dyn Node, context: &mut RenderContext) -> RenderResult {
+    let result = block.render(context)?;
+    if block.has_nolinebreak_end() {
+        if let Some(r) = result.strip_suffix('\n') {
+            return Ok(String::from(r));
+        }
+    }
+    Ok(result)
+}
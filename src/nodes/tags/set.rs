@@ -0,0 +1,262 @@
+use crate::common::variable::Variable;
+use crate::context::build_context::BuildContext;
+use crate::context::render_context::RenderContext;
+use crate::engine::{NodeBuildResult, NodeBuildData, RenderResult};
+use crate::expressions;
+use crate::expressions::nodes::{Node as ExpressionNode};
+use crate::error::template_error::TemplateError;
+use crate::nodes::{BaseNode, Node};
+use crate::nodes::tags::{render_block, TAG_END, TAG_START};
+use crate::nodes::container::ContainerNode;
+use crate::parsers::expression_parser::{ parse_expression_string, ParseExpressionStringResult };
+use crate::parsers::tag_parser::{get_keyword, split_names, GetKeywordResult};
+
+use derive_macro::HasBaseNode;
+
+const SET_KEYWORD: &str = "set";
+const ENDSET_KEYWORD: &str = "endset";
+
+/// `{% set name = expression %}`, `{% set a, b = 1, 2 %}`, or `{% set name %}...{% endset %}`
+/// which assigns the rendered block. The only child is the captured block
+#[derive(HasBaseNode)]
+pub struct SetNode {
+    base_node: BaseNode,
+    names: Vec<String>,
+    // Values with their offsets in the template. Empty for a captured block
+    values: Vec<(usize, Box<dyn ExpressionNode>)>,
+}
+
+impl SetNode {
+    fn create() -> SetNode {
+        SetNode {
+            base_node: BaseNode::new(),
+            names: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    pub fn try_create_from_template(template: &String) -> Option<Box<dyn Node>> {
+        let keyword = match get_keyword(template) {
+            Some(r) => r.keyword,
+            None => return None,
+        };
+        match keyword {
+            SET_KEYWORD => Some(Box::from(SetNode::create())),
+            _ => None
+        }
+    }
+
+    fn build_block_set(&mut self, context: &BuildContext, get_keyword_result: &GetKeywordResult) -> NodeBuildResult {
+        self.base_node.start_offset = context.offset;
+        let parsed_expression = match parse_expression_string(&get_keyword_result.remain,
+                get_keyword_result.end_pos, TAG_END) {
+            Ok(s) => Ok(s) as Result<ParseExpressionStringResult, TemplateError>,
+            Err(s) => return Err(TemplateError::create(
+                context.template.clone(),
+                context.offset,
+                s))
+        }?;
+
+        let header = &parsed_expression.expression_string;
+        let header_offset = context.offset + get_keyword_result.end_pos;
+        // Names cannot contain "=", so the first one separates names from values
+        let names_end = header.find('=').unwrap_or(header.len());
+        self.names = match split_names(&header[..names_end]) {
+            Some(names) => names,
+            None => return Err(TemplateError::create(
+                context.template.clone(),
+                header_offset,
+                format!("An error in the Set Node. Expected names separated by commas, got: {}", header[..names_end].trim()))),
+        };
+
+        if names_end == header.len() {
+            return self.build_block_capture(context, &parsed_expression);
+        }
+
+        let values_offset = header_offset + names_end + 1;
+        let values = match expressions::split_list_items(&header[names_end + 1..], values_offset) {
+            Ok(values) => values,
+            Err(err) => return Err(TemplateError::create(
+                context.template.clone(),
+                err.offset,
+                format!("An error in the Set Node. {}", err.message))),
+        };
+        if values.len() != self.names.len() {
+            return Err(TemplateError::create(
+                context.template.clone(),
+                values_offset,
+                format!("An error in the Set Node. Expected {} values, got {}", self.names.len(), values.len())));
+        }
+        for (start, value) in values {
+            match expressions::parse(String::from(value), &context.parsing_context) {
+                Ok(expr_node) => self.values.push((values_offset + start, expr_node)),
+                Err(err) => return Err(TemplateError::create(
+                    context.template.clone(),
+                    values_offset + start + err.offset,
+                    format!("An error in the Set Node. Failed to parse an expression: {}", err.message))),
+            }
+        }
+
+        self.base_node.end_offset = context.offset + parsed_expression.end_offset;
+        self.base_node.has_nolinebreak_end = parsed_expression.has_nolinebreak_end;
+        Ok(NodeBuildData::new(parsed_expression.end_offset, false, parsed_expression.has_nolinebreak_end))
+    }
+
+    fn build_block_capture(&mut self, context: &BuildContext, parsed_expression: &ParseExpressionStringResult) -> NodeBuildResult {
+        if self.names.len() != 1 {
+            return Err(TemplateError::create(
+                context.template.clone(),
+                context.offset,
+                String::from("An error in the Set Node. A block can be assigned to a single name only")));
+        }
+
+        // The block starts after `-%}` of the tag, and ends before `{%-` of the closing tag, see `build`
+        let mut container = ContainerNode::create();
+        let container_base_node = container.get_base_node_mut();
+        container_base_node.has_nolinebreak_beginning = parsed_expression.has_nolinebreak_end;
+        container_base_node.start_offset = context.offset + parsed_expression.end_offset + 1;
+        container_base_node.end_offset = container_base_node.start_offset;
+        self.base_node.children.push(Box::from(container));
+        Ok(NodeBuildData::new(parsed_expression.end_offset, true, parsed_expression.has_nolinebreak_end))
+    }
+
+    fn build_block_end(&mut self, context: &BuildContext) -> NodeBuildResult {
+        match expressions::get_end_offset(&context.template_remain[TAG_START.len()..], TAG_END) {
+            Some(end_pos) => {
+                let end_pos = TAG_START.len() + end_pos;
+                let has_nolinebreak_end = context.template_remain[..end_pos-TAG_END.len()+1].ends_with('-');
+                self.base_node.end_offset = context.offset + end_pos;
+                self.base_node.has_nolinebreak_end = has_nolinebreak_end;
+                Ok(NodeBuildData::new(end_pos, false, has_nolinebreak_end))
+            },
+            None => Err(TemplateError::create(
+                context.template.clone(),
+                context.offset,
+                String::from("Cannot find closing tag."))),
+        }
+    }
+}
+
+impl Node for SetNode {
+    fn add_child(&mut self, child: Box<dyn Node>) {
+        match self.base_node.children.last_mut() {
+            None => {},
+            Some(c) => {
+                c.get_base_node_mut().set_end_offset(child.get_base_node().end_offset);
+                c.add_child(child);
+            },
+        }
+    }
+
+    fn build(&mut self, context: &BuildContext) -> NodeBuildResult {
+        let result = match get_keyword(&context.template_remain) {
+            Some(r) => Ok(r),
+            None => Err(TemplateError::create(
+                context.template.clone(),
+                context.offset,
+                String::from("Unknown keyword. Expected: (set|endset)"))),
+        }?;
+
+        match self.get_children_mut().last_mut() {
+            Some(child) => child.get_base_node_mut().has_nolinebreak_end = result.has_nolinebreak_beginning,
+            None => self.get_base_node_mut().has_nolinebreak_beginning = result.has_nolinebreak_beginning,
+        };
+        match result.keyword {
+            SET_KEYWORD => self.build_block_set(context, &result),
+            ENDSET_KEYWORD => self.build_block_end(context),
+            _ => Err(TemplateError::create(
+                context.template.clone(),
+                context.offset,
+                String::from("Unknown keyword. Expected: (set|endset)"))),
+        }
+    }
+
+    fn is_continuation(&self, context: &BuildContext) -> bool {
+        match get_keyword(&context.template_remain) {
+            Some(r) => ENDSET_KEYWORD == r.keyword,
+            None => false,
+        }
+    }
+
+    fn render(&self, context: &mut RenderContext) -> RenderResult {
+        if let Some(block) = self.base_node.children.first() {
+            let value = Variable::from(render_block(block.as_ref(), context)?);
+            context.set_variable(&self.names[0], value);
+            return Ok(String::new());
+        }
+
+        // All values are evaluated before assigning, so `{% set a, b = b, a %}` swaps the values
+        let mut values = Vec::new();
+        for (offset, value) in &self.values {
+            match value.evaluate(context) {
+                Ok(v) => values.push(v),
+                Err(err) => return Err(TemplateError::create(
+                    context.template.clone(),
+                    match err.offset {
+                        Some(err_offset) => offset + err_offset,
+                        None => self.base_node.start_offset,
+                    },
+                    format!("Failed to evaluate an expression: {}", err.message))),
+            }
+        }
+        for (name, value) in self.names.iter().zip(values) {
+            context.set_variable(name, value);
+        }
+        Ok(String::new())
+    }
+
+    fn get_name(&self) -> &str {
+        "set"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(template: &str) -> Result<SetNode, TemplateError> {
+        let mut node = SetNode::create();
+        let mut context = BuildContext::new();
+        context.template = String::from(template);
+        context.template_remain = String::from(template);
+        node.build(&context).map(|_| node)
+    }
+
+    #[test]
+    fn test_nodes_tags_set_try_create() {
+        assert!(SetNode::try_create_from_template(&String::from("{% set x = 1 %}")).is_some());
+        assert!(SetNode::try_create_from_template(&String::from("{% settings %}")).is_none());
+    }
+
+    #[test]
+    fn test_nodes_tags_set_render() {
+        let node = build("{% set a, b = b, a + 1 %}").unwrap();
+        assert_eq!(node.values.iter().map(|(offset, _)| *offset).collect::<Vec<_>>(), vec![13, 16]);
+
+        let mut context = RenderContext::new();
+        context.set_parameter("a", Variable::new_from_int(1));
+        context.set_parameter("b", Variable::new_from_int(2));
+        match node.render(&mut context) {
+            Ok(string) => assert_eq!(string, ""),
+            Err(e) => panic!("Expected to render a node, but got an error: {}", e),
+        }
+        assert_eq!(context.parameters.get("a"), Some(&Variable::new_from_int(2)));
+        assert_eq!(context.parameters.get("b"), Some(&Variable::new_from_int(2)));
+    }
+
+    #[test]
+    fn test_nodes_tags_set_build_errors() {
+        match build("{% set a, b = 1 %}") {
+            Ok(_) => panic!("Building must have failed."),
+            Err(e) => assert_eq!(e.message, "An error in the Set Node. Expected 2 values, got 1"),
+        }
+        match build("{% set a.b = 1 %}") {
+            Ok(_) => panic!("Building must have failed."),
+            Err(e) => assert_eq!(e.message, "An error in the Set Node. Expected names separated by commas, got: a.b"),
+        }
+        match build("{% set a, b %}") {
+            Ok(_) => panic!("Building must have failed."),
+            Err(e) => assert_eq!(e.message, "An error in the Set Node. A block can be assigned to a single name only"),
+        }
+    }
+}
@@ -38,7 +38,7 @@ pub struct GetKeywordResult<'a> {
 /// assert_eq!(result.keyword, "if");
 /// assert_eq!(result.remain, String::from(" 1 + 1 %}"));
 /// ```
-pub fn get_keyword(string: &String) -> Option<GetKeywordResult> {
+pub fn get_keyword(string: &String) -> Option<GetKeywordResult<'_>> {
     let s = match string.strip_prefix(TAG_START) {
         Some(m) => m,
         None => return None,
@@ -60,4 +60,24 @@ pub fn get_keyword(string: &String) -> Option<GetKeywordResult> {
         keyword: &s[..end_pos_rel],
         remain,
     })
-}
\ No newline at end of file
+}
+
+/// Splits a comma separated list of variable names, e.g. targets of `{% for key, value in map %}`.
+/// Returns `None` if a name is not a valid variable name
+///
+/// # Examples
+///
+/// ```
+/// use pete_core::parsers::tag_parser::split_names;
+///
+/// assert_eq!(split_names(" key, value "), Some(vec![String::from("key"), String::from("value")]));
+/// assert_eq!(split_names("a, 1b"), None);
+/// assert_eq!(split_names("a,"), None);
+/// ```
+pub fn split_names(string: &str) -> Option<Vec<String>> {
+    let names: Vec<String> = string.split(',').map(|n| String::from(n.trim())).collect();
+    let is_valid = names.iter().all(|n| {
+        n.starts_with(|c: char| c.is_alphabetic() || '_' == c) && n.chars().all(|c| c.is_alphanumeric() || '_' == c)
+    });
+    if is_valid { Some(names) } else { None }
+}
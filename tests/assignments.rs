@@ -0,0 +1,46 @@
+use std::fs;
+
+use pete_core::engine::Engine;
+use pete_core::common::variable::{VariableStore, Variable};
+
+fn read_test_files(input: &str, output: &str) -> (String, String) {
+    let input = match fs::read_to_string(input) {
+        Ok(s) => s,
+        Err(_) => panic!("Cannot read input file: {}", input),
+    };
+    let output = match fs::read_to_string(output) {
+        Ok(s) => s,
+        Err(_) => panic!("Cannot read output file: {}", output),
+    };
+    (input, output)
+}
+
+fn create_variables() -> VariableStore {
+    let mut user = VariableStore::new();
+    user.insert(String::from("id"), Variable::new_from_int(7));
+    user.insert(String::from("first"), Variable::new_from_str("John"));
+    user.insert(String::from("last"), Variable::new_from_str("Smith"));
+    let mut variables = VariableStore::new();
+    variables.insert(String::from("user"), Variable::new_from_map(user));
+    variables
+}
+
+#[test]
+fn test_assignments_simple() {
+    let engine = Engine::new();
+    let (input, output) = read_test_files("tests/templates/assignments/simple.input.twig", "tests/templates/assignments/simple.output.txt");
+    match engine.render(input, create_variables()) {
+        Ok(string) => assert_eq!(string, output),
+        Err(e) => panic!("Error: {}", &e.message)
+    }
+}
+
+#[test]
+fn test_assignments_capture() {
+    let engine = Engine::new();
+    let (input, output) = read_test_files("tests/templates/assignments/capture.input.twig", "tests/templates/assignments/capture.output.txt");
+    match engine.render(input, create_variables()) {
+        Ok(string) => assert_eq!(string, output),
+        Err(e) => panic!("Error: {}", &e.message)
+    }
+}
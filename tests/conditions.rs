@@ -43,4 +43,16 @@ fn test_conditions_nolinebreak() {
         Ok(string) => assert_eq!(string, output),
         Err(e) => panic!("Error: {}", &e.message)
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_conditions_comparison() {
+    let engine = Engine::new();
+    let mut variables = VariableStore::new();
+    variables.insert(String::from("myvar"), Variable::new_from_int(2));
+    let (input, output) = read_test_files("tests/templates/conditions/comparison.input.twig", "tests/templates/conditions/comparison.output.txt");
+    match engine.render(input, variables) {
+        Ok(string) => assert_eq!(string, output),
+        Err(e) => panic!("Error: {}", &e.message)
+    }
+}
@@ -0,0 +1,76 @@
+use std::fs;
+
+use pete_core::engine::Engine;
+use pete_core::common::variable::{VariableStore, Variable};
+use pete_core::loaders::filesystem::FilesystemLoader;
+
+fn create_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_loader(FilesystemLoader::new("tests/templates/includes"));
+    engine
+}
+
+fn read_test_files(input: &str, output: &str) -> (String, String) {
+    let input = match fs::read_to_string(input) {
+        Ok(s) => s,
+        Err(_) => panic!("Cannot read input file: {}", input),
+    };
+    let output = match fs::read_to_string(output) {
+        Ok(s) => s,
+        Err(_) => panic!("Cannot read output file: {}", output),
+    };
+    (input, output)
+}
+
+#[test]
+fn test_includes_page() {
+    let engine = create_engine();
+    let mut variables = VariableStore::new();
+    variables.insert(String::from("user"), Variable::new_from_str("John"));
+    variables.insert(String::from("items"), Variable::new_from_list(vec![Variable::new_from_str("a"), Variable::new_from_str("b")]));
+    let (input, output) = read_test_files("tests/templates/includes/page.input.twig", "tests/templates/includes/page.output.txt");
+    match engine.render(input, variables) {
+        Ok(string) => assert_eq!(string, output),
+        Err(e) => panic!("Error: {}", e)
+    }
+}
+
+#[test]
+fn test_includes_errors() {
+    let engine = create_engine();
+    match engine.render(String::from("Page\n{% include 'partials/broken.twig' %}"), VariableStore::new()) {
+        Ok(_) => panic!("Rendering must have failed."),
+        Err(e) => {
+            assert_eq!(e.filename, Some(String::from("partials/broken.twig")));
+            assert_eq!(e.to_string(), "An error ocurred in partials/broken.twig at line 2, position 3: Failed to evaluate an expression: Variable not found: missing");
+        },
+    }
+
+    match engine.render(String::from("Page\n{% include 'partials/unknown.twig' %}"), VariableStore::new()) {
+        Ok(_) => panic!("Rendering must have failed."),
+        Err(e) => {
+            assert_eq!(e.filename, None);
+            assert_eq!(e.to_string(), "An error ocurred at line 2, position 0: Template not found: partials/unknown.twig");
+        },
+    }
+
+    match engine.render(String::from("{% include 'partials/recursive.twig' %}"), VariableStore::new()) {
+        Ok(_) => panic!("Rendering must have failed."),
+        Err(e) => {
+            assert_eq!(e.filename, Some(String::from("partials/recursive.twig")));
+            assert_eq!(e.message, "Cannot include \"partials/recursive.twig\", templates are nested deeper than 64 levels");
+        },
+    }
+
+    for (template, message) in [
+        ("{{ include('partials/header.twig', {}, 1) }}", "Expected 1 or 2 arguments, got 3"),
+        ("{{ include() }}", "Expected 1 or 2 arguments, got 0"),
+        ("{{ include(1) }}", "A template name must be a string, got int"),
+        ("{{ include('partials/header.twig', 1) }}", "Variables must be a map, got int"),
+    ].iter() {
+        match engine.render(String::from(*template), VariableStore::new()) {
+            Ok(_) => panic!("Rendering must have failed."),
+            Err(e) => assert_eq!(e.message, format!("Failed to evaluate an expression: Function \"include\" failed: {}", message)),
+        }
+    }
+}
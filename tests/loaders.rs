@@ -0,0 +1,101 @@
+use pete_core::engine::Engine;
+use pete_core::common::variable::{VariableStore, Variable};
+use pete_core::context::render_context::RenderContext;
+use pete_core::expressions::errors::evaluation_error::EvaluationError;
+use pete_core::expressions::functions::NamedArgs;
+use pete_core::loaders::chain::ChainLoader;
+use pete_core::loaders::filesystem::FilesystemLoader;
+use pete_core::loaders::memory::MemoryLoader;
+
+fn create_variables() -> VariableStore {
+    let mut variables = VariableStore::new();
+    variables.insert(String::from("user"), Variable::new_from_str("John"));
+    variables
+}
+
+#[test]
+fn test_loaders_render_name() {
+    let mut theme = MemoryLoader::new();
+    theme.add_template("emails/welcome.twig", "{% include 'emails/header.twig' %}Welcome, {{ user }}!");
+    theme.add_template("emails/header.twig", "[Theme] ");
+    let mut loader = ChainLoader::new();
+    loader.add_loader(theme);
+    loader.add_loader(FilesystemLoader::new("tests/templates/includes"));
+
+    let mut engine = Engine::new();
+    engine.set_loader(loader);
+    for _ in 0..2 {
+        match engine.render_name("emails/welcome.twig", create_variables()) {
+            Ok(string) => assert_eq!(string, "[Theme] Welcome, John!"),
+            Err(e) => panic!("Error: {}", e),
+        }
+    }
+    match engine.render_name("partials/header.twig", create_variables()) {
+        Ok(_) => panic!("Rendering must have failed."),
+        Err(e) => assert_eq!(e.filename, Some(String::from("partials/header.twig"))),
+    }
+}
+
+#[test]
+fn test_loaders_render_name_errors() {
+    let engine = Engine::new();
+    match engine.render_name("emails/welcome.twig", VariableStore::new()) {
+        Ok(_) => panic!("Rendering must have failed."),
+        Err(e) => assert_eq!(e.message, "Cannot load the template \"emails/welcome.twig\", no template loader is set"),
+    }
+
+    let mut engine = Engine::new();
+    engine.set_loader(FilesystemLoader::new("tests/templates/includes/partials"));
+    match engine.render_name("../page.input.twig", VariableStore::new()) {
+        Ok(_) => panic!("Rendering must have failed."),
+        Err(e) => assert_eq!(e.message, "Invalid template name \"../page.input.twig\", it must be a path inside of the template directories"),
+    }
+    match engine.render_name("unknown.twig", VariableStore::new()) {
+        Ok(_) => panic!("Rendering must have failed."),
+        Err(e) => {
+            assert_eq!(e.filename, None);
+            assert_eq!(e.message, "Template not found: unknown.twig");
+        },
+    }
+}
+
+
+#[test]
+fn test_loaders_render_name_after_registry_changes() {
+    fn one(_args: &[Variable], _named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+        Ok(Variable::new_from_str("one"))
+    }
+    fn two(_args: &[Variable], _named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+        Ok(Variable::new_from_str("two"))
+    }
+    fn stars(value: &Variable, _args: &[Variable], _named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+        Ok(Variable::new_from_string(format!("*{}*", value.get_string_value())))
+    }
+    fn dashes(value: &Variable, _args: &[Variable], _named_args: &NamedArgs, _context: &RenderContext) -> Result<Variable, EvaluationError> {
+        Ok(Variable::new_from_string(format!("-{}-", value.get_string_value())))
+    }
+
+    let mut loader = MemoryLoader::new();
+    loader.add_template("g", "{{ g()|mark }}");
+    loader.add_template("page", "{% include 'g' %}");
+    let mut engine = Engine::new();
+    engine.set_loader(loader);
+    engine.add_function("g", one);
+    engine.add_filter("mark", stars);
+    match engine.render_name("page", VariableStore::new()) {
+        Ok(string) => assert_eq!(string, "*one*"),
+        Err(e) => panic!("Error: {}", e),
+    }
+
+    engine.add_function("g", two);
+    match engine.render_name("page", VariableStore::new()) {
+        Ok(string) => assert_eq!(string, "*two*"),
+        Err(e) => panic!("Error: {}", e),
+    }
+
+    engine.add_filter("mark", dashes);
+    match engine.render_name("page", VariableStore::new()) {
+        Ok(string) => assert_eq!(string, "-two-"),
+        Err(e) => panic!("Error: {}", e),
+    }
+}
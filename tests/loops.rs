@@ -0,0 +1,72 @@
+use std::fs;
+
+use pete_core::engine::Engine;
+use pete_core::common::variable::{VariableStore, Variable};
+
+fn read_test_files(input: &str, output: &str) -> (String, String) {
+    let input = match fs::read_to_string(input) {
+        Ok(s) => s,
+        Err(_) => panic!("Cannot read input file: {}", input),
+    };
+    let output = match fs::read_to_string(output) {
+        Ok(s) => s,
+        Err(_) => panic!("Cannot read output file: {}", output),
+    };
+    (input, output)
+}
+
+fn create_user(name: &str, active: bool) -> Variable {
+    let mut user = VariableStore::new();
+    user.insert(String::from("name"), Variable::new_from_str(name));
+    user.insert(String::from("active"), Variable::new_from_boolean(active));
+    Variable::new_from_map(user)
+}
+
+#[test]
+fn test_loops_simple() {
+    let engine = Engine::new();
+    let mut settings = VariableStore::new();
+    settings.insert(String::from("lang"), Variable::new_from_str("en"));
+    settings.insert(String::from("theme"), Variable::new_from_str("dark"));
+    let mut variables = VariableStore::new();
+    variables.insert(String::from("users"), Variable::new_from_list(vec![
+        create_user("John", true),
+        create_user("Jane", false),
+        create_user("Jack", true),
+    ]));
+    variables.insert(String::from("settings"), Variable::new_from_map(settings));
+    variables.insert(String::from("tags"), Variable::new_from_list(Vec::new()));
+    let (input, output) = read_test_files("tests/templates/loops/simple.input.twig", "tests/templates/loops/simple.output.txt");
+    match engine.render(input, variables) {
+        Ok(string) => assert_eq!(string, output),
+        Err(e) => panic!("Error: {}", &e.message)
+    }
+}
+
+#[test]
+fn test_loops_nolinebreak() {
+    let engine = Engine::new();
+    let mut variables = VariableStore::new();
+    variables.insert(String::from("items"), Variable::new_from_list(vec![Variable::new_from_str("a"), Variable::new_from_str("b")]));
+    let (input, output) = read_test_files("tests/templates/loops/nolinebreak.input.twig", "tests/templates/loops/nolinebreak.output.txt");
+    match engine.render(input, variables) {
+        Ok(string) => assert_eq!(string, output),
+        Err(e) => panic!("Error: {}", &e.message)
+    }
+}
+
+#[test]
+fn test_loops_scope() {
+    let mut engine = Engine::new();
+    engine.add_global("site", Variable::new_from_str("Blog"));
+    engine.add_global("title", Variable::new_from_str("Untitled"));
+    let mut variables = VariableStore::new();
+    variables.insert(String::from("items"), Variable::new_from_list(vec![Variable::new_from_int(1), Variable::new_from_int(2), Variable::new_from_int(3)]));
+    variables.insert(String::from("item"), Variable::new_from_str("outer"));
+    variables.insert(String::from("title"), Variable::new_from_str("Home"));
+    let (input, output) = read_test_files("tests/templates/loops/scope.input.twig", "tests/templates/loops/scope.output.txt");
+    match engine.render(input, variables) {
+        Ok(string) => assert_eq!(string, output),
+        Err(e) => panic!("Error: {}", &e.message)
+    }
+}
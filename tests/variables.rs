@@ -1,7 +1,7 @@
 use std::fs;
 
 use pete_core::engine::Engine;
-use pete_core::common::variable::{VariableStore, Variable};
+use pete_core::common::variable::{ToVariable, VariableStore, Variable};
 
 fn read_test_files(input: &str, output: &str) -> (String, String) {
     let input = match fs::read_to_string(input) {
@@ -25,4 +25,120 @@ fn test_variables_simple() {
         Ok(string) => assert_eq!(string, output),
         Err(e) => panic!("Error: {}", &e.message)
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_variables_struct() {
+    let engine = Engine::new();
+    let mut address = VariableStore::new();
+    address.insert(String::from("city"), Variable::new_from_str("Paris"));
+    address.insert(String::from("zip"), Variable::new_from_int(75001));
+    let mut user = VariableStore::new();
+    user.insert(String::from("name"), Variable::new_from_str("John"));
+    user.insert(String::from("address"), Variable::new_from_struct(address));
+    let mut variables = VariableStore::new();
+    variables.insert(String::from("user"), Variable::new_from_struct(user));
+    let (input, output) = read_test_files("tests/templates/variables/struct.input.twig", "tests/templates/variables/struct.output.txt");
+    match engine.render(input, variables) {
+        Ok(string) => assert_eq!(string, output),
+        Err(e) => panic!("Error: {}", &e.message)
+    }
+}
+
+#[derive(ToVariable)]
+struct Address {
+    city: &'static str,
+    #[pete(skip)]
+    #[allow(dead_code)]
+    zip: u32,
+}
+
+#[derive(ToVariable)]
+enum Role {
+    Admin,
+    #[allow(dead_code)]
+    #[pete(rename = "guest")]
+    Guest,
+}
+
+#[derive(ToVariable)]
+enum Status {
+    #[allow(dead_code)]
+    Active,
+    Banned { days: u8 },
+}
+
+#[derive(ToVariable)]
+enum Contact {
+    Email(String),
+    Phone(u8, &'static str),
+}
+
+#[derive(ToVariable)]
+struct Point(i32, i32);
+
+#[derive(ToVariable)]
+struct Tagged<T> {
+    label: &'static str,
+    value: T,
+}
+
+#[derive(ToVariable)]
+struct User {
+    #[pete(rename = "login")]
+    name: String,
+    #[pete(skip)]
+    #[allow(dead_code)]
+    password: String,
+    role: Role,
+    status: Status,
+    #[pete(flatten)]
+    address: Address,
+    contacts: Vec<Contact>,
+    point: Point,
+    tagged: Option<Tagged<i64>>,
+}
+
+#[test]
+fn test_variables_derive() {
+    let engine = Engine::new();
+    let user = User {
+        name: String::from("john"),
+        password: String::from("secret"),
+        role: Role::Admin,
+        status: Status::Banned { days: 3 },
+        address: Address { city: "Paris", zip: 75001 },
+        contacts: vec![Contact::Email(String::from("john@example.com")), Contact::Phone(33, "123")],
+        point: Point(1, 2),
+        tagged: Some(Tagged { label: "x", value: 5 }),
+    };
+    let user = user.to_variable();
+    let keys: Vec<&String> = user.get_map_value().unwrap().keys().collect();
+    assert_eq!(keys, vec!["login", "role", "status", "city", "contacts", "point", "tagged"]);
+    let mut variables = VariableStore::new();
+    variables.insert(String::from("user"), user);
+    let (input, output) = read_test_files("tests/templates/variables/derive.input.twig", "tests/templates/variables/derive.output.txt");
+    match engine.render(input, variables) {
+        Ok(string) => assert_eq!(string, output),
+        Err(e) => panic!("Error: {}", &e.message)
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_variables_json_document() {
+    let engine = Engine::new();
+    let document = match fs::read_to_string("tests/templates/variables/document.json") {
+        Ok(s) => s,
+        Err(_) => panic!("Cannot read the document"),
+    };
+    let variables = match pete_core::common::document::from_json(&document) {
+        Ok(v) => v,
+        Err(e) => panic!("Error: {}", &e.message),
+    };
+    let (input, output) = read_test_files("tests/templates/variables/document.input.twig", "tests/templates/variables/document.output.txt");
+    match engine.render(input, variables) {
+        Ok(string) => assert_eq!(string, output),
+        Err(e) => panic!("Error: {}", &e.message)
+    }
+}